@@ -0,0 +1,66 @@
+use std::{sync::Arc, time::Instant};
+
+use super::{Agent, AgentError};
+use crate::core::{
+    AgeDistribution, AgeEstimate, Attribute, Clock, InferenceInput, InferenceSignal, SignalSource,
+};
+
+// Turns self-identified input into an authoritative signal
+pub struct SelfReportAgent {
+    clock: Arc<dyn Clock>,
+}
+
+impl SelfReportAgent {
+    pub fn new(clock: Arc<dyn Clock>) -> Self {
+        Self { clock }
+    }
+}
+
+impl Agent for SelfReportAgent {
+    fn source(&self) -> SignalSource {
+        SignalSource::SelfReported
+    }
+
+    fn attributes(&self) -> &'static [Attribute] {
+        &[Attribute::Gender, Attribute::Age]
+    }
+
+    async fn analyze(&self, input: &InferenceInput) -> Result<InferenceSignal, AgentError> {
+        let start = Instant::now();
+        let mut signal = InferenceSignal::new(SignalSource::SelfReported);
+        let declared = &input.declared;
+
+        if let Some(gender) = declared.gender() {
+            signal.gender = Some(gender);
+            signal.self_reported.push(Attribute::Gender);
+            signal.explain(
+                Some(Attribute::Gender),
+                "self_reported_gender",
+                "Gender taken from self-identified pronouns or gender identity.",
+            );
+        }
+
+        let today = self.clock.today();
+        if let Some(year) = declared.birth_year(today) {
+            signal.birth_year = Some(year);
+            signal.birth_year_certain = true;
+            signal.age = Some(AgeDistribution::from_birth_year(year, today));
+            signal.age_estimate = Some(AgeEstimate::from_birth_year(year, today));
+            signal.self_reported.push(Attribute::Age);
+            signal.explain(
+                Some(Attribute::Age),
+                "self_reported_birth_year",
+                format!("Birth year {year} self-reported."),
+            );
+        } else if let Some(year) = declared.birth_year {
+            signal.warn(
+                Some(Attribute::Age),
+                "invalid_birth_year",
+                format!("Ignored implausible self-reported birth year {year}."),
+            );
+        }
+
+        signal.latency_ms = start.elapsed().as_millis() as u64;
+        Ok(signal)
+    }
+}
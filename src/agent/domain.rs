@@ -1,22 +1,37 @@
-use std::time::Instant;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::{sync::Arc, time::Instant};
 
-use super::Agent;
-use crate::core::{InferenceInput, InferenceSignal, OrganizationIntelligence, SignalSource};
+use super::{
+    Agent, AgentError,
+    llm::{ChatRequest, LlmProvider, MessageContent, ResponseFormat, strip_markdown},
+};
+use crate::core::{
+    Attribute, InferenceInput, InferenceSignal, OrganizationIntelligence, SignalSource,
+};
+
+// Employees often, but far from always, live in the HQ country
+const HQ_REGION_PROBABILITY: f64 = 0.5;
+
+#[derive(Deserialize, JsonSchema)]
+struct DomainResult {
+    name: Option<String>,
+    category: Option<String>,
+    employee_count: Option<String>,
+    employee_count_source: Option<String>,
+    hq_country: Option<String>,
+}
 
 pub struct DomainAgent {
-    client: reqwest::Client,
-    api_key: String,
+    llm: Arc<dyn LlmProvider>,
 }
 
 impl DomainAgent {
-    pub fn new(api_key: String) -> Self {
-        Self {
-            client: reqwest::Client::new(),
-            api_key,
-        }
+    pub fn new(llm: Arc<dyn LlmProvider>) -> Self {
+        Self { llm }
     }
 
-    async fn enrich_domain(&self, domain: &str) -> Option<OrganizationIntelligence> {
+    async fn enrich_domain(&self, domain: &str) -> Result<OrganizationIntelligence, AgentError> {
         let prompt = format!(
             r#"Given the email domain "{}", provide organization intelligence.
 
@@ -27,73 +42,87 @@ Return JSON only, no markdown:
   "name": "Full org name with parent relationship if applicable" or null,
   "category": "Industry / Sub-category" or null,
   "employee_count": "~X employees" or null,
-  "employee_count_source": "Source name" or null
+  "employee_count_source": "Source name" or null,
+  "hq_country": "ISO 3166-1 alpha-2 code of the headquarters country" or null
 }}
             
 If you can't confidently identify the organization, return all nulls."#,
             domain
         );
 
-        let body = serde_json::json!({
-            "model": "gpt-4o-mini",
-            "messages": [
-                {"role": "user", "content": prompt}
-            ],
-            "temperature": 0.1,
-            "max_tokens": 200
-        });
-
-        let resp = self
-            .client
-            .post("https://api.openai.com/v1/chat/completions")
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(&body)
-            .send()
-            .await
-            .ok()?;
-
-        let json: serde_json::Value = resp.json().await.ok()?;
-        let content = json["choices"][0]["message"]["content"].as_str()?;
-
-        let clean = content
-            .trim()
-            .trim_start_matches("```json")
-            .trim_start_matches("```")
-            .trim_end_matches("```")
-            .trim();
-
-        let parsed: serde_json::Value = serde_json::from_str(clean).ok()?;
-
-        Some(OrganizationIntelligence {
-            domain: domain.to_string(),
-            name: parsed["name"].as_str().map(String::from),
-            category: parsed["category"].as_str().map(String::from),
-            employee_count: parsed["employee_count"].as_str().map(String::from),
-            employee_count_source: parsed["employee_count_source"].as_str().map(String::from),
+        let request = ChatRequest {
+            max_tokens: Some(200),
+            response_format: Some(ResponseFormat::json_schema::<DomainResult>("domain_result")),
+            ..ChatRequest::user(MessageContent::Text(prompt), 0.1)
+        };
+
+        let response = self.llm.chat(&request).await?;
+        let choice = response
+            .choices
+            .first()
+            .ok_or_else(|| AgentError::Provider {
+                message: "no choices in response".to_string(),
+            })?;
+        let clean = strip_markdown(choice.content()?);
+
+        let parsed: DomainResult =
+            serde_json::from_str(clean).map_err(|e| AgentError::MalformedOutput {
+                message: e.to_string(),
+            })?;
+
+        Ok(OrganizationIntelligence {
+            name: parsed.name,
+            category: parsed.category,
+            employee_count: parsed.employee_count,
+            employee_count_source: parsed.employee_count_source,
+            hq_country: parsed
+                .hq_country
+                .filter(|c| c.len() == 2)
+                .map(|c| c.to_uppercase()),
+            ..OrganizationIntelligence::new(domain)
         })
     }
 }
 
 impl Agent for DomainAgent {
-    async fn analyze(&self, input: &InferenceInput) -> InferenceSignal {
+    fn source(&self) -> SignalSource {
+        SignalSource::Domain
+    }
+
+    fn attributes(&self) -> &'static [Attribute] {
+        &[Attribute::Organization, Attribute::Region]
+    }
+
+    async fn analyze(&self, input: &InferenceInput) -> Result<InferenceSignal, AgentError> {
         let start = Instant::now();
         let mut signal = InferenceSignal::new(SignalSource::Domain);
 
-        if let Some(email) = &input.email {
-            if let Some(domain) = email.split('@').nth(1) {
-                if let Some(org) = self.enrich_domain(domain).await {
-                    signal.reasoning.push(format!(
-                        "Domain {} enriched: {} ({})",
-                        domain,
-                        org.name.as_deref().unwrap_or("unknown"),
-                        org.category.as_deref().unwrap_or("unknown")
-                    ));
-                    signal.organization = Some(org);
-                }
+        if let Some(email) = &input.email
+            && let Some(domain) = email.split('@').nth(1)
+        {
+            let org = self.enrich_domain(domain).await?;
+            signal.explain(
+                Some(Attribute::Organization),
+                "domain_enriched",
+                format!(
+                    "Domain {} enriched: {} ({})",
+                    domain,
+                    org.name.as_deref().unwrap_or("unknown"),
+                    org.category.as_deref().unwrap_or("unknown")
+                ),
+            );
+            if let Some(country) = &org.hq_country {
+                signal.region.insert(country.clone(), HQ_REGION_PROBABILITY);
+                signal.explain(
+                    Some(Attribute::Region),
+                    "region_from_org_hq",
+                    format!("Region {country} suggested by organization HQ."),
+                );
             }
+            signal.organization = Some(org);
         }
 
         signal.latency_ms = start.elapsed().as_millis() as u64;
-        signal
+        Ok(signal)
     }
 }
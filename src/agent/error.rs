@@ -0,0 +1,100 @@
+use serde::Serialize;
+use std::{fmt, time::Duration};
+
+// Why an agent produced no signal at all, as opposed to finding no evidence
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AgentError {
+    Timeout,
+    // The upstream asked for a wait that would overrun the agent's deadline
+    DeadlineExceeded {
+        retry_after_ms: u64,
+    },
+    Network {
+        message: String,
+    },
+    HttpStatus {
+        status: u16,
+        message: String,
+        // From a Retry-After header
+        #[serde(skip)]
+        retry_after: Option<Duration>,
+    },
+    // The provider answered but reported an error or an unreadable envelope
+    Provider {
+        message: String,
+    },
+    // The model's answer did not follow the requested format
+    MalformedOutput {
+        message: String,
+    },
+    Refusal {
+        message: String,
+    },
+}
+
+impl AgentError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Timeout => "timeout",
+            Self::DeadlineExceeded { .. } => "deadline_exceeded",
+            Self::Network { .. } => "request_failed",
+            Self::HttpStatus { .. } => "http_status",
+            Self::Provider { .. } => "api_error",
+            Self::MalformedOutput { .. } => "malformed_output",
+            Self::Refusal { .. } => "refusal",
+        }
+    }
+
+    // Transient failures worth another attempt
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Timeout | Self::Network { .. } => true,
+            Self::HttpStatus { status, .. } => *status == 429 || *status >= 500,
+            _ => false,
+        }
+    }
+
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::HttpStatus { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AgentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Timeout => write!(f, "Request timed out"),
+            Self::DeadlineExceeded { retry_after_ms } => {
+                write!(f, "Retry-After of {retry_after_ms} ms exceeds the deadline")
+            }
+            Self::Network { message } => write!(f, "Request failed: {message}"),
+            Self::HttpStatus {
+                status, message, ..
+            } => write!(f, "HTTP {status}: {message}"),
+            Self::Provider { message } => write!(f, "API error: {message}"),
+            Self::MalformedOutput { message } => write!(f, "Parse error: {message}"),
+            Self::Refusal { message } => write!(f, "Model refused: {message}"),
+        }
+    }
+}
+
+impl From<reqwest::Error> for AgentError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            return Self::Timeout;
+        }
+        match e.status() {
+            Some(status) => Self::HttpStatus {
+                status: status.as_u16(),
+                message: e.to_string(),
+                retry_after: None,
+            },
+            None => Self::Network {
+                message: e.to_string(),
+            },
+        }
+    }
+}
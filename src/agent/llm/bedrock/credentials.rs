@@ -0,0 +1,79 @@
+use std::env;
+
+use super::sigv4::Credentials;
+
+// Standard AWS lookup: environment variables first, then the shared
+// credentials file for `profile`, `$AWS_PROFILE` or `default`
+pub fn load(profile: Option<&str>) -> Option<Credentials> {
+    from_env().or_else(|| {
+        let path = env::var("AWS_SHARED_CREDENTIALS_FILE").ok().or_else(|| {
+            env::var("HOME")
+                .ok()
+                .map(|h| format!("{h}/.aws/credentials"))
+        })?;
+        let profile = profile
+            .map(String::from)
+            .or_else(|| env::var("AWS_PROFILE").ok())
+            .unwrap_or_else(|| "default".to_string());
+
+        from_profile(&std::fs::read_to_string(path).ok()?, &profile)
+    })
+}
+
+fn from_env() -> Option<Credentials> {
+    Some(Credentials {
+        access_key_id: env::var("AWS_ACCESS_KEY_ID").ok()?,
+        secret_access_key: env::var("AWS_SECRET_ACCESS_KEY").ok()?,
+        session_token: env::var("AWS_SESSION_TOKEN").ok(),
+    })
+}
+
+fn from_profile(file: &str, profile: &str) -> Option<Credentials> {
+    let mut section = None;
+    let (mut key_id, mut secret, mut token) = (None, None, None);
+
+    for line in file.lines().map(str::trim) {
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = Some(name.trim());
+            continue;
+        }
+        if section != Some(profile) {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            let value = Some(value.trim().to_string());
+            match key.trim() {
+                "aws_access_key_id" => key_id = value,
+                "aws_secret_access_key" => secret = value,
+                "aws_session_token" => token = value,
+                _ => {}
+            }
+        }
+    }
+
+    Some(Credentials {
+        access_key_id: key_id?,
+        secret_access_key: secret?,
+        session_token: token,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile_is_read_from_its_own_section() {
+        let file = "[default]\naws_access_key_id = AKIADEFAULT\naws_secret_access_key = one\n\n\
+                    [ci]\naws_access_key_id=AKIACI\naws_secret_access_key=two\naws_session_token=tok\n";
+
+        let ci = from_profile(file, "ci").unwrap();
+        assert_eq!(ci.access_key_id, "AKIACI");
+        assert_eq!(ci.session_token.as_deref(), Some("tok"));
+        assert_eq!(
+            from_profile(file, "default").unwrap().secret_access_key,
+            "one"
+        );
+        assert!(from_profile(file, "missing").is_none());
+    }
+}
@@ -0,0 +1,164 @@
+use reqwest::{Client, Url, redirect::Policy};
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::Duration,
+};
+
+use crate::agent::AgentError;
+
+// Converse's per-image limit
+pub const MAX_BYTES: usize = 3_750_000;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+// Downloads a client-supplied image on the server's behalf, so only https URLs
+// to public addresses are fetched. The checked addresses are pinned for the
+// connection and redirects are refused, so neither DNS nor the remote host can
+// steer the request somewhere else.
+pub async fn fetch(url: &str) -> Result<(String, Vec<u8>), AgentError> {
+    let parsed = Url::parse(url).map_err(|e| rejected(format!("invalid URL: {e}")))?;
+    if parsed.scheme() != "https" {
+        return Err(rejected("only https URLs are fetched".to_string()));
+    }
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| rejected("no host".to_string()))?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_string();
+    let port = parsed.port_or_known_default().unwrap_or(443);
+
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), port))
+        .await
+        .map_err(|e| AgentError::Network {
+            message: format!("cannot resolve {host}: {e}"),
+        })?
+        .collect();
+    if addrs.is_empty() || !addrs.iter().all(|a| is_public(a.ip())) {
+        return Err(rejected(format!("{host} is not a public host")));
+    }
+
+    let client = Client::builder()
+        .timeout(TIMEOUT)
+        .redirect(Policy::none())
+        .resolve_to_addrs(&host, &addrs)
+        .build()?;
+    let mut response = client.get(parsed).send().await?.error_for_status()?;
+    if response.status().is_redirection() {
+        return Err(rejected("redirects are not followed".to_string()));
+    }
+    if response
+        .content_length()
+        .is_some_and(|len| len > MAX_BYTES as u64)
+    {
+        return Err(too_large());
+    }
+
+    let media_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+
+    // The declared length may be missing or wrong
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if bytes.len() + chunk.len() > MAX_BYTES {
+            return Err(too_large());
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    Ok((media_type, bytes))
+}
+
+fn rejected(reason: String) -> AgentError {
+    AgentError::Provider {
+        message: format!("image URL rejected: {reason}"),
+    }
+}
+
+pub fn too_large() -> AgentError {
+    AgentError::Provider {
+        message: format!("image exceeds {MAX_BYTES} bytes"),
+    }
+}
+
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(v4) => is_public_v4(v4),
+            None => is_public_v6(ip),
+        },
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        // Carrier-grade NAT, and "this network"
+        || (a == 100 && (64..128).contains(&b))
+        || a == 0
+        // Reserved for future use
+        || a >= 240)
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        // Unique local and link-local
+        || (first & 0xfe00) == 0xfc00
+        || (first & 0xffc0) == 0xfe80)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_public_addresses_pass() {
+        for ip in ["8.8.8.8", "2606:4700::1111"] {
+            assert!(is_public(ip.parse().unwrap()), "{ip}");
+        }
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:169.254.169.254",
+        ] {
+            assert!(!is_public(ip.parse().unwrap()), "{ip}");
+        }
+    }
+
+    #[ntex::test]
+    async fn internal_and_plain_http_urls_are_refused() {
+        for url in [
+            "http://169.254.169.254/latest/meta-data/",
+            "https://169.254.169.254/latest/meta-data/",
+            "https://127.0.0.1/avatar.png",
+            "https://[::1]/avatar.png",
+            "https://localhost/avatar.png",
+            "file:///etc/passwd",
+        ] {
+            let error = fetch(url).await.unwrap_err();
+            assert!(error.to_string().contains("rejected"), "{url}: {error}");
+        }
+    }
+}
@@ -0,0 +1,383 @@
+mod credentials;
+mod image;
+pub mod sigv4;
+mod r#type;
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use futures::future::{BoxFuture, join_all};
+use reqwest::{Client, Url};
+use std::collections::BTreeMap;
+
+use super::{
+    ChatRequest, ChatResponse, Choice, Content, LlmProvider, MessageContent, ResponseMessage,
+    Usage, retry,
+};
+use crate::agent::AgentError;
+use sigv4::Credentials;
+use r#type::*;
+
+const SERVICE: &str = "bedrock";
+
+// Amazon Bedrock through the model-agnostic Converse API, signed with SigV4
+#[derive(Debug, Clone)]
+pub struct BedrockProvider {
+    client: Client,
+    endpoint: String,
+    model: String,
+    region: String,
+    headers: BTreeMap<String, String>,
+    credentials: Credentials,
+}
+
+impl BedrockProvider {
+    pub fn new(
+        endpoint: Option<String>,
+        model: String,
+        region: String,
+        headers: BTreeMap<String, String>,
+        credentials: Credentials,
+    ) -> Self {
+        Self {
+            client: Client::new(),
+            endpoint: endpoint
+                .unwrap_or_else(|| format!("https://bedrock-runtime.{region}.amazonaws.com")),
+            model,
+            region,
+            headers,
+            credentials,
+        }
+    }
+
+    pub fn from_env(
+        endpoint: Option<String>,
+        model: String,
+        region: Option<String>,
+        profile: Option<&str>,
+        headers: BTreeMap<String, String>,
+    ) -> Result<Self, String> {
+        let region = region
+            .or_else(|| std::env::var("AWS_REGION").ok())
+            .or_else(|| std::env::var("AWS_DEFAULT_REGION").ok())
+            .ok_or("no region configured and AWS_REGION is not set")?;
+        let credentials = credentials::load(profile).ok_or("no AWS credentials found")?;
+
+        Ok(Self::new(endpoint, model, region, headers, credentials))
+    }
+
+    // Converse returns a single answer, so `n` choices take `n` calls
+    async fn chat_all(&self, request: &ChatRequest) -> Result<ChatResponse, AgentError> {
+        let body = serde_json::to_vec(&self.converse_request(request).await?).map_err(|e| {
+            AgentError::Provider {
+                message: e.to_string(),
+            }
+        })?;
+
+        let calls = request.n.unwrap_or(1).max(1);
+        let answers = join_all((0..calls).map(|_| self.converse(&body))).await;
+
+        let mut response = ChatResponse {
+            choices: Vec::new(),
+            usage: None,
+        };
+        for answer in answers {
+            let (choice, usage) = answer?;
+            response.choices.push(choice);
+            if let Some(usage) = usage {
+                let total = response.usage.get_or_insert(Usage {
+                    total_tokens: 0,
+                    completion_tokens: 0,
+                });
+                total.total_tokens += usage.total_tokens;
+                total.completion_tokens += usage.completion_tokens;
+            }
+        }
+        Ok(response)
+    }
+
+    async fn converse_request(&self, request: &ChatRequest) -> Result<ConverseRequest, AgentError> {
+        let mut messages = Vec::new();
+        for message in &request.messages {
+            let content = match &message.content {
+                MessageContent::Text(text) => vec![ContentBlock::Text(text.clone())],
+                MessageContent::Parts(parts) => {
+                    let mut blocks = Vec::new();
+                    for part in parts {
+                        blocks.push(match part {
+                            Content::Text { text } => ContentBlock::Text(text.clone()),
+                            Content::ImageUrl { image_url } => {
+                                ContentBlock::Image(self.image(&image_url.url).await?)
+                            }
+                        });
+                    }
+                    blocks
+                }
+            };
+            messages.push(BedrockMessage {
+                role: message.role.clone(),
+                content,
+            });
+        }
+
+        // Converse has no structured output, so the schema goes in the prompt
+        if let Some(format) = &request.response_format
+            && let Some(last) = messages.last_mut()
+        {
+            last.content.push(ContentBlock::Text(format!(
+                "Respond with only a JSON object matching this JSON schema:\n{}",
+                format.schema()
+            )));
+        }
+
+        Ok(ConverseRequest {
+            messages,
+            inference_config: InferenceConfig {
+                max_tokens: request.max_tokens,
+                temperature: request.temperature,
+            },
+        })
+    }
+
+    // Converse takes image bytes rather than URLs
+    async fn image(&self, url: &str) -> Result<ImageBlock, AgentError> {
+        let (media_type, bytes) = match url.strip_prefix("data:") {
+            Some(data) => {
+                let (media_type, encoded) =
+                    data.split_once(";base64,")
+                        .ok_or_else(|| AgentError::Provider {
+                            message: "unsupported data URL".to_string(),
+                        })?;
+                if encoded.len() / 4 * 3 > image::MAX_BYTES {
+                    return Err(image::too_large());
+                }
+                (media_type.to_string(), encoded.to_string())
+            }
+            None => {
+                let (media_type, bytes) = image::fetch(url).await?;
+                (media_type, STANDARD.encode(bytes))
+            }
+        };
+
+        let format = ["png", "jpeg", "gif", "webp"]
+            .into_iter()
+            .find(|f| media_type.contains(f))
+            .or_else(|| {
+                let path = url.split(['?', '#']).next().unwrap_or(url).to_lowercase();
+                match path.rsplit('.').next() {
+                    Some("jpg" | "jpeg") => Some("jpeg"),
+                    Some("png") => Some("png"),
+                    Some("gif") => Some("gif"),
+                    Some("webp") => Some("webp"),
+                    _ => None,
+                }
+            })
+            .unwrap_or("jpeg");
+
+        Ok(ImageBlock {
+            format: format.to_string(),
+            source: ImageSource { bytes },
+        })
+    }
+
+    async fn converse(&self, body: &[u8]) -> Result<(Choice, Option<Usage>), AgentError> {
+        let url = format!(
+            "{}/model/{}/converse",
+            self.endpoint.trim_end_matches('/'),
+            sigv4::uri_encode(&self.model)
+        );
+        let parsed = Url::parse(&url).map_err(|e| AgentError::Provider {
+            message: format!("invalid endpoint {url}: {e}"),
+        })?;
+        let host = match parsed.port() {
+            Some(port) => format!("{}:{port}", parsed.host_str().unwrap_or_default()),
+            None => parsed.host_str().unwrap_or_default().to_string(),
+        };
+
+        let amz_date = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        let mut signed = vec![
+            ("content-type".to_string(), "application/json".to_string()),
+            ("host".to_string(), host),
+            ("x-amz-date".to_string(), amz_date.clone()),
+        ];
+        if let Some(token) = &self.credentials.session_token {
+            signed.push(("x-amz-security-token".to_string(), token.clone()));
+        }
+
+        let canonical = sigv4::canonical_request("POST", parsed.path(), "", &signed, body);
+        let signature = sigv4::signature(
+            &self.credentials.secret_access_key,
+            &amz_date,
+            &self.region,
+            SERVICE,
+            &canonical,
+        );
+        let authorization = sigv4::authorization(
+            &self.credentials,
+            &amz_date,
+            &self.region,
+            SERVICE,
+            &signed,
+            &signature,
+        );
+
+        // reqwest derives `host` from the URL
+        let mut builder = self
+            .client
+            .post(parsed)
+            .header("authorization", authorization)
+            .body(body.to_vec());
+        for (name, value) in signed.iter().filter(|(name, _)| name != "host") {
+            builder = builder.header(name, value);
+        }
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+
+        let response = builder.send().await?;
+        let status = response.status();
+        let retry_after = retry::retry_after(response.headers());
+        let text = response.text().await?;
+
+        if !status.is_success() {
+            let message = serde_json::from_str::<BedrockError>(&text).map_or(text, |e| e.message);
+            return Err(AgentError::HttpStatus {
+                status: status.as_u16(),
+                message,
+                retry_after,
+            });
+        }
+
+        let converse: ConverseResponse =
+            serde_json::from_str(&text).map_err(|e| AgentError::Provider {
+                message: format!("unreadable response: {e}"),
+            })?;
+
+        let content = converse.output.message.map(|m| {
+            m.content
+                .into_iter()
+                .filter_map(|b| b.text)
+                .collect::<Vec<_>>()
+                .join("")
+        });
+        let finish_reason = converse.stop_reason.map(|reason| {
+            match reason.as_str() {
+                "content_filtered" | "guardrail_intervened" => "content_filter",
+                "max_tokens" => "length",
+                _ => "stop",
+            }
+            .to_string()
+        });
+
+        let choice = Choice {
+            message: ResponseMessage {
+                content,
+                refusal: None,
+            },
+            finish_reason,
+            logprobs: None,
+        };
+        let usage = converse.usage.map(|u| Usage {
+            total_tokens: u.total_tokens,
+            completion_tokens: u.output_tokens,
+        });
+        Ok((choice, usage))
+    }
+}
+
+impl LlmProvider for BedrockProvider {
+    fn chat<'a>(
+        &'a self,
+        request: &'a ChatRequest,
+    ) -> BoxFuture<'a, Result<ChatResponse, AgentError>> {
+        Box::pin(self.chat_all(request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::llm::{ImageUrl, stand_in};
+
+    fn header<'a>(head: &'a str, name: &str) -> &'a str {
+        head.lines()
+            .find_map(|l| {
+                let (n, v) = l.split_once(':')?;
+                n.eq_ignore_ascii_case(name).then_some(v.trim())
+            })
+            .unwrap()
+    }
+
+    #[ntex::test]
+    async fn converse_request_is_signed() {
+        let credentials = Credentials {
+            access_key_id: "AKIDEXAMPLE".to_string(),
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string(),
+            session_token: Some("session".to_string()),
+        };
+        let body = r#"{"output": {"message": {"role": "assistant", "content": [{"text": "{}"}]}},
+            "stopReason": "end_turn", "usage": {"inputTokens": 9, "outputTokens": 3, "totalTokens": 12}}"#;
+        let (url, server) = stand_in::serve(200, &[], body).await;
+
+        let provider = BedrockProvider::new(
+            Some(url),
+            "anthropic.claude-3-haiku-20240307-v1:0".to_string(),
+            "us-east-1".to_string(),
+            BTreeMap::new(),
+            credentials.clone(),
+        );
+        let request = ChatRequest::user(
+            MessageContent::Parts(vec![
+                Content::Text {
+                    text: "Describe".to_string(),
+                },
+                Content::ImageUrl {
+                    image_url: ImageUrl {
+                        url: "data:image/png;base64,iVBORw0K".to_string(),
+                        detail: "low".to_string(),
+                    },
+                },
+            ]),
+            0.1,
+        );
+        let response = provider.chat(&request).await.unwrap();
+        let sent = server.await.unwrap();
+
+        assert_eq!(response.choices[0].content(), Ok("{}"));
+        assert_eq!(response.choices[0].finish_reason.as_deref(), Some("stop"));
+        let usage = response.usage.unwrap();
+        assert_eq!((usage.total_tokens, usage.completion_tokens), (12, 3));
+
+        let (head, payload) = sent.split_once("\r\n\r\n").unwrap();
+        let path = head.split(' ').nth(1).unwrap();
+        assert_eq!(
+            path,
+            "/model/anthropic.claude-3-haiku-20240307-v1%3A0/converse"
+        );
+        assert!(payload.contains(r#"{"image":{"format":"png","source":{"bytes":"iVBORw0K"}}}"#));
+
+        let amz_date = header(head, "x-amz-date");
+        let signed: Vec<(String, String)> =
+            ["content-type", "host", "x-amz-date", "x-amz-security-token"]
+                .into_iter()
+                .map(|name| (name.to_string(), header(head, name).to_string()))
+                .collect();
+        let canonical = sigv4::canonical_request("POST", path, "", &signed, payload.as_bytes());
+        let signature = sigv4::signature(
+            &credentials.secret_access_key,
+            amz_date,
+            "us-east-1",
+            SERVICE,
+            &canonical,
+        );
+        assert_eq!(
+            header(head, "authorization"),
+            sigv4::authorization(
+                &credentials,
+                amz_date,
+                "us-east-1",
+                SERVICE,
+                &signed,
+                &signature
+            )
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use ring::{digest, hmac};
+use std::fmt;
+
+// AWS Signature Version 4, enough for JSON POSTs to a regional service
+
+pub const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+
+#[derive(Clone, PartialEq)]
+pub struct Credentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field("access_key_id", &self.access_key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+pub fn sha256_hex(data: &[u8]) -> String {
+    hex(digest::digest(&digest::SHA256, data).as_ref())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn sign(key: &[u8], data: &str) -> Vec<u8> {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, key);
+    hmac::sign(&key, data.as_bytes()).as_ref().to_vec()
+}
+
+// Percent-encodes everything but RFC 3986 unreserved characters
+pub fn uri_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+// `path` is as sent on the wire; services other than S3 encode it once more.
+// `headers` must be lowercase names, sorted.
+pub fn canonical_request(
+    method: &str,
+    path: &str,
+    query: &str,
+    headers: &[(String, String)],
+    payload: &[u8],
+) -> String {
+    let path: Vec<String> = path.split('/').map(uri_encode).collect();
+    let canonical_headers: String = headers
+        .iter()
+        .map(|(name, value)| format!("{name}:{}\n", value.trim()))
+        .collect();
+
+    format!(
+        "{method}\n{}\n{query}\n{canonical_headers}\n{}\n{}",
+        path.join("/"),
+        signed_headers(headers),
+        sha256_hex(payload)
+    )
+}
+
+pub fn signed_headers(headers: &[(String, String)]) -> String {
+    headers
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+pub fn scope(amz_date: &str, region: &str, service: &str) -> String {
+    format!("{}/{region}/{service}/aws4_request", &amz_date[..8])
+}
+
+pub fn signature(
+    secret_access_key: &str,
+    amz_date: &str,
+    region: &str,
+    service: &str,
+    canonical_request: &str,
+) -> String {
+    let string_to_sign = format!(
+        "{ALGORITHM}\n{amz_date}\n{}\n{}",
+        scope(amz_date, region, service),
+        sha256_hex(canonical_request.as_bytes())
+    );
+
+    let key = sign(
+        format!("AWS4{secret_access_key}").as_bytes(),
+        &amz_date[..8],
+    );
+    let key = sign(&key, region);
+    let key = sign(&key, service);
+    let key = sign(&key, "aws4_request");
+    hex(&sign(&key, &string_to_sign))
+}
+
+pub fn authorization(
+    credentials: &Credentials,
+    amz_date: &str,
+    region: &str,
+    service: &str,
+    headers: &[(String, String)],
+    signature: &str,
+) -> String {
+    format!(
+        "{ALGORITHM} Credential={}/{}, SignedHeaders={}, Signature={signature}",
+        credentials.access_key_id,
+        scope(amz_date, region, service),
+        signed_headers(headers)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The IAM ListUsers example from the AWS Signature Version 4 documentation
+    #[test]
+    fn signature_matches_aws_example() {
+        let headers = [
+            (
+                "content-type",
+                "application/x-www-form-urlencoded; charset=utf-8",
+            ),
+            ("host", "iam.amazonaws.com"),
+            ("x-amz-date", "20150830T123600Z"),
+        ]
+        .map(|(n, v)| (n.to_string(), v.to_string()));
+
+        let canonical = canonical_request(
+            "GET",
+            "/",
+            "Action=ListUsers&Version=2010-05-08",
+            &headers,
+            b"",
+        );
+        assert_eq!(
+            sha256_hex(canonical.as_bytes()),
+            "f536975d06c0309214f805bb90ccff089219ecd68b2577efef23edd43b7e1a59"
+        );
+
+        let signature = signature(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20150830T123600Z",
+            "us-east-1",
+            "iam",
+            &canonical,
+        );
+        assert_eq!(
+            signature,
+            "5d672d79c15b13162d9279b0855cfba6789a8edb4c82c400e06b5924a6f2b5d7"
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConverseRequest {
+    pub messages: Vec<BedrockMessage>,
+    pub inference_config: InferenceConfig,
+}
+
+#[derive(Serialize)]
+pub struct BedrockMessage {
+    pub role: String,
+    pub content: Vec<ContentBlock>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentBlock {
+    Text(String),
+    Image(ImageBlock),
+}
+
+#[derive(Serialize)]
+pub struct ImageBlock {
+    pub format: String,
+    pub source: ImageSource,
+}
+
+#[derive(Serialize)]
+pub struct ImageSource {
+    // Base64-encoded image bytes
+    pub bytes: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InferenceConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    pub temperature: f64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConverseResponse {
+    pub output: Output,
+    pub stop_reason: Option<String>,
+    pub usage: Option<BedrockUsage>,
+}
+
+#[derive(Deserialize)]
+pub struct Output {
+    pub message: Option<OutputMessage>,
+}
+
+#[derive(Deserialize)]
+pub struct OutputMessage {
+    pub content: Vec<OutputBlock>,
+}
+
+#[derive(Deserialize)]
+pub struct OutputBlock {
+    pub text: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BedrockUsage {
+    pub output_tokens: u32,
+    pub total_tokens: u32,
+}
+
+#[derive(Deserialize)]
+pub struct BedrockError {
+    pub message: String,
+}
@@ -0,0 +1,129 @@
+pub mod bedrock;
+mod openai;
+pub mod retry;
+mod schema;
+#[cfg(test)]
+pub mod stand_in;
+mod r#type;
+
+use futures::future::BoxFuture;
+use serde::Deserialize;
+use std::{collections::BTreeMap, fmt, sync::Arc};
+
+use super::AgentError;
+
+pub use bedrock::BedrockProvider;
+pub use openai::OpenAiProvider;
+pub use retry::{RetryConfig, Retrying};
+pub use schema::ResponseFormat;
+pub use r#type::*;
+
+pub const OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+pub const OPENAI_MODEL: &str = "gpt-4o-mini";
+
+// A chat-completions backend shared by the LLM agents. Boxed futures keep it
+// usable as `dyn LlmProvider`, so the backend can be chosen from config.
+pub trait LlmProvider: Send + Sync + fmt::Debug {
+    fn chat<'a>(
+        &'a self,
+        request: &'a ChatRequest,
+    ) -> BoxFuture<'a, Result<ChatResponse, AgentError>>;
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderKind {
+    #[default]
+    OpenAi,
+    Bedrock,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct LlmConfig {
+    pub provider: ProviderKind,
+    // Defaults to the provider's public endpoint
+    pub base_url: Option<String>,
+    // Defaults to gpt-4o-mini for OpenAI; Bedrock needs a model id
+    pub model: Option<String>,
+    // Sent with every request, e.g. gateway routing or tenant headers
+    pub headers: BTreeMap<String, String>,
+    // Bedrock only; fall back to AWS_REGION and AWS_PROFILE
+    pub region: Option<String>,
+    pub profile: Option<String>,
+}
+
+impl LlmConfig {
+    // Panics on setups that could never serve a request
+    pub fn build(&self) -> Arc<dyn LlmProvider> {
+        match self.provider {
+            ProviderKind::OpenAi => {
+                // Self-hosted OpenAI-compatible servers often need no key
+                let api_key = std::env::var("OPENAI_API_KEY").ok();
+                if api_key.is_none() && self.base_url.is_none() {
+                    panic!(
+                        "OPEN_API_KEY environment variable must be set.\n `export OPENAI_API_KEY='...'`"
+                    );
+                }
+                Arc::new(OpenAiProvider::new(self.clone(), api_key))
+            }
+            ProviderKind::Bedrock => {
+                let model = self
+                    .model
+                    .clone()
+                    .unwrap_or_else(|| panic!("The bedrock provider needs a model id"));
+                let provider = BedrockProvider::from_env(
+                    self.base_url.clone(),
+                    model,
+                    self.region.clone(),
+                    self.profile.as_deref(),
+                    self.headers.clone(),
+                )
+                .unwrap_or_else(|e| panic!("Cannot set up the bedrock provider: {e}"));
+                Arc::new(provider)
+            }
+        }
+    }
+}
+
+// Per-agent overrides of the shared `llm` config
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AgentLlmConfig {
+    pub onomastic: Option<LlmConfig>,
+    pub vision: Option<LlmConfig>,
+    pub domain: Option<LlmConfig>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LlmProviders {
+    pub onomastic: Arc<dyn LlmProvider>,
+    pub vision: Arc<dyn LlmProvider>,
+    pub domain: Arc<dyn LlmProvider>,
+}
+
+impl LlmProviders {
+    pub fn new(shared: &LlmConfig, agents: &AgentLlmConfig) -> Self {
+        let shared = shared.build();
+        let select = |config: &Option<LlmConfig>| {
+            config
+                .as_ref()
+                .map_or_else(|| shared.clone(), LlmConfig::build)
+        };
+
+        Self {
+            onomastic: select(&agents.onomastic),
+            vision: select(&agents.vision),
+            domain: select(&agents.domain),
+        }
+    }
+}
+
+pub fn strip_markdown(content: &str) -> &str {
+    content
+        .trim()
+        .trim_start_matches("```json")
+        .trim_start_matches("```")
+        .trim_end_matches("```")
+        .trim()
+}
@@ -0,0 +1,150 @@
+use futures::future::BoxFuture;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    ChatRequest, ChatResponse, LlmConfig, LlmProvider, OPENAI_BASE_URL, OPENAI_MODEL, retry,
+};
+use crate::agent::AgentError;
+
+// Any server speaking the OpenAI chat-completions protocol
+#[derive(Debug, Clone)]
+pub struct OpenAiProvider {
+    client: Client,
+    config: LlmConfig,
+    api_key: Option<String>,
+}
+
+#[derive(Serialize)]
+struct WireRequest<'a> {
+    model: &'a str,
+    #[serde(flatten)]
+    request: &'a ChatRequest,
+}
+
+#[derive(Deserialize)]
+struct WireResponse {
+    #[serde(flatten)]
+    response: ChatResponse,
+    error: Option<ApiError>,
+}
+
+#[derive(Deserialize)]
+struct ApiError {
+    message: String,
+}
+
+impl OpenAiProvider {
+    pub fn new(config: LlmConfig, api_key: Option<String>) -> Self {
+        Self {
+            client: Client::new(),
+            config,
+            api_key,
+        }
+    }
+
+    async fn complete(&self, request: &ChatRequest) -> Result<ChatResponse, AgentError> {
+        let base_url = self.config.base_url.as_deref().unwrap_or(OPENAI_BASE_URL);
+        let url = format!("{}/chat/completions", base_url.trim_end_matches('/'));
+        let mut builder = self.client.post(url).json(&WireRequest {
+            model: self.config.model.as_deref().unwrap_or(OPENAI_MODEL),
+            request,
+        });
+        if let Some(key) = &self.api_key {
+            builder = builder.bearer_auth(key);
+        }
+        for (name, value) in &self.config.headers {
+            builder = builder.header(name, value);
+        }
+
+        let response = builder.send().await?;
+        let status = response.status();
+        let retry_after = retry::retry_after(response.headers());
+        let body = response.text().await?;
+
+        if !status.is_success() {
+            let message = serde_json::from_str::<WireResponse>(&body)
+                .ok()
+                .and_then(|w| w.error)
+                .map_or(body, |e| e.message);
+            return Err(AgentError::HttpStatus {
+                status: status.as_u16(),
+                message,
+                retry_after,
+            });
+        }
+
+        let wire: WireResponse = serde_json::from_str(&body).map_err(|e| AgentError::Provider {
+            message: format!("unreadable response: {e}"),
+        })?;
+        match wire.error {
+            Some(error) => Err(AgentError::Provider {
+                message: error.message,
+            }),
+            None => Ok(wire.response),
+        }
+    }
+}
+
+impl LlmProvider for OpenAiProvider {
+    fn chat<'a>(
+        &'a self,
+        request: &'a ChatRequest,
+    ) -> BoxFuture<'a, Result<ChatResponse, AgentError>> {
+        Box::pin(self.complete(request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::llm::{MessageContent, stand_in};
+
+    fn provider(base_url: String) -> OpenAiProvider {
+        let config = LlmConfig {
+            base_url: Some(base_url),
+            model: Some("local-model".to_string()),
+            headers: [("x-gateway-route".to_string(), "aura".to_string())].into(),
+            ..LlmConfig::default()
+        };
+        OpenAiProvider::new(config, Some("secret".to_string()))
+    }
+
+    fn request() -> ChatRequest {
+        ChatRequest::user(MessageContent::Text("hi".to_string()), 0.1)
+    }
+
+    #[ntex::test]
+    async fn chat_against_configured_endpoint() {
+        let body = r#"{"choices": [{"message": {"content": "{}"}}], "usage": {"total_tokens": 7}}"#;
+        let (url, server) = stand_in::serve(200, &[], body).await;
+
+        let response = provider(format!("{url}/v1/"))
+            .chat(&request())
+            .await
+            .unwrap();
+        let sent = server.await.unwrap();
+
+        assert_eq!(response.choices[0].content(), Ok("{}"));
+        assert!(sent.starts_with("POST /v1/chat/completions "));
+        assert!(sent.contains("x-gateway-route: aura"));
+        assert!(sent.contains("authorization: Bearer secret"));
+        assert!(sent.contains(r#""model":"local-model""#));
+    }
+
+    #[ntex::test]
+    async fn http_errors_are_typed() {
+        let body = r#"{"error": {"message": "rate limited"}}"#;
+        let (url, _server) = stand_in::serve(429, &[("retry-after", "2")], body).await;
+
+        let error = provider(url).chat(&request()).await.unwrap_err();
+        assert_eq!(
+            error,
+            AgentError::HttpStatus {
+                status: 429,
+                message: "rate limited".to_string(),
+                retry_after: Some(std::time::Duration::from_secs(2)),
+            }
+        );
+    }
+}
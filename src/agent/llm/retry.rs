@@ -0,0 +1,258 @@
+use futures::future::BoxFuture;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::Deserialize;
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU32, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use super::{ChatRequest, ChatResponse, LlmProvider};
+use crate::agent::AgentError;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    // Per attempt
+    pub timeout_ms: u64,
+    // Per agent, across every attempt and wait
+    pub deadline_ms: u64,
+    // Further attempts after timeouts, network errors, 429 and 5xx
+    pub retries: u32,
+    // Retry k waits a random time up to base * 2^k, capped at max. Retry-After
+    // replaces the backoff when it fits before the deadline.
+    pub base_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            timeout_ms: 15_000,
+            deadline_ms: 20_000,
+            retries: 2,
+            base_backoff_ms: 250,
+            max_backoff_ms: 4_000,
+        }
+    }
+}
+
+impl RetryPolicy {
+    fn backoff(&self, retry: u32) -> Duration {
+        let ceiling = self
+            .base_backoff_ms
+            .saturating_mul(1 << retry.min(16))
+            .min(self.max_backoff_ms);
+        Duration::from_millis((ceiling as f64 * jitter()) as u64)
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct RetryConfig {
+    pub onomastic: RetryPolicy,
+    pub vision: RetryPolicy,
+    pub domain: RetryPolicy,
+}
+
+// Uniform in [0, 1)
+fn jitter() -> f64 {
+    let mut bytes = [0; 8];
+    match SystemRandom::new().fill(&mut bytes) {
+        Ok(()) => (u64::from_le_bytes(bytes) >> 11) as f64 / (1u64 << 53) as f64,
+        Err(_) => 0.5,
+    }
+}
+
+// Delay-seconds or an HTTP date
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let wait = date.signed_duration_since(chrono::Utc::now());
+    Some(wait.to_std().unwrap_or(Duration::ZERO))
+}
+
+// Wraps one agent's provider for one request, counting the attempts it makes
+#[derive(Debug)]
+pub struct Retrying {
+    inner: Arc<dyn LlmProvider>,
+    policy: RetryPolicy,
+    attempts: AtomicU32,
+}
+
+impl Retrying {
+    pub fn new(inner: Arc<dyn LlmProvider>, policy: RetryPolicy) -> Self {
+        Self {
+            inner,
+            policy,
+            attempts: AtomicU32::new(0),
+        }
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts.load(Ordering::Relaxed)
+    }
+
+    async fn chat_with_retries(&self, request: &ChatRequest) -> Result<ChatResponse, AgentError> {
+        let timeout = Duration::from_millis(self.policy.timeout_ms);
+        let deadline = Instant::now() + Duration::from_millis(self.policy.deadline_ms);
+        let mut retry = 0;
+
+        loop {
+            self.attempts.fetch_add(1, Ordering::Relaxed);
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let attempt = tokio::time::timeout(timeout.min(remaining), self.inner.chat(request));
+            let error = match attempt.await {
+                Ok(Ok(response)) => return Ok(response),
+                Ok(Err(error)) => error,
+                Err(_) => AgentError::Timeout,
+            };
+            if retry >= self.policy.retries || !error.is_retryable() {
+                return Err(error);
+            }
+
+            // A wait that would overrun the deadline ends the retries
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let delay = error
+                .retry_after()
+                .unwrap_or_else(|| self.policy.backoff(retry));
+            if delay >= remaining {
+                return Err(match error.retry_after() {
+                    Some(wait) => AgentError::DeadlineExceeded {
+                        retry_after_ms: wait.as_millis() as u64,
+                    },
+                    None => error,
+                });
+            }
+            tokio::time::sleep(delay).await;
+            retry += 1;
+        }
+    }
+}
+
+impl LlmProvider for Retrying {
+    fn chat<'a>(
+        &'a self,
+        request: &'a ChatRequest,
+    ) -> BoxFuture<'a, Result<ChatResponse, AgentError>> {
+        Box::pin(self.chat_with_retries(request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::llm::MessageContent;
+    use std::sync::Mutex;
+
+    // Replays scripted outcomes, one per call; `None` hangs past the timeout
+    #[derive(Debug)]
+    struct Scripted(Mutex<Vec<Option<Result<(), AgentError>>>>);
+
+    impl LlmProvider for Scripted {
+        fn chat<'a>(
+            &'a self,
+            _: &'a ChatRequest,
+        ) -> BoxFuture<'a, Result<ChatResponse, AgentError>> {
+            let next = self.0.lock().unwrap().remove(0);
+            Box::pin(async move {
+                let Some(outcome) = next else {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    unreachable!("the attempt should have timed out");
+                };
+                outcome.map(|()| ChatResponse {
+                    choices: Vec::new(),
+                    usage: None,
+                })
+            })
+        }
+    }
+
+    fn status(status: u16, retry_after: Option<Duration>) -> Option<Result<(), AgentError>> {
+        Some(Err(AgentError::HttpStatus {
+            status,
+            message: String::new(),
+            retry_after,
+        }))
+    }
+
+    async fn attempts(
+        script: Vec<Option<Result<(), AgentError>>>,
+    ) -> (Result<(), AgentError>, u32) {
+        let policy = RetryPolicy {
+            timeout_ms: 20,
+            deadline_ms: 100,
+            retries: 3,
+            base_backoff_ms: 1,
+            max_backoff_ms: 10,
+        };
+        let retrying = Retrying::new(Arc::new(Scripted(Mutex::new(script))), policy);
+        let request = ChatRequest::user(MessageContent::Text("hi".to_string()), 0.1);
+
+        let result = retrying.chat(&request).await.map(|_| ());
+        (result, retrying.attempts())
+    }
+
+    #[ntex::test]
+    async fn transient_failures_are_retried() {
+        let script = vec![
+            None,
+            status(503, None),
+            status(429, Some(Duration::ZERO)),
+            Some(Ok(())),
+        ];
+        assert_eq!(attempts(script).await, (Ok(()), 4));
+    }
+
+    #[ntex::test]
+    async fn permanent_failures_stop_early() {
+        let (result, tries) = attempts(vec![status(400, None)]).await;
+        assert!(matches!(
+            result,
+            Err(AgentError::HttpStatus { status: 400, .. })
+        ));
+        assert_eq!(tries, 1);
+    }
+
+    #[ntex::test]
+    async fn retry_after_is_honoured_within_the_deadline() {
+        // Longer than the backoff cap, but it fits
+        let wait = Some(Duration::from_millis(60));
+        let script = vec![status(429, wait), Some(Ok(()))];
+        assert_eq!(attempts(script).await, (Ok(()), 2));
+
+        // The second wait would overrun the deadline
+        let script = vec![status(429, wait), status(429, wait)];
+        let expected = Err(AgentError::DeadlineExceeded { retry_after_ms: 60 });
+        assert_eq!(attempts(script).await, (expected, 2));
+
+        let long_wait = status(429, Some(Duration::from_secs(60)));
+        let expected = Err(AgentError::DeadlineExceeded {
+            retry_after_ms: 60_000,
+        });
+        assert_eq!(attempts(vec![long_wait]).await, (expected, 1));
+    }
+
+    #[ntex::test]
+    async fn deadline_cuts_attempts_short() {
+        let policy = RetryPolicy {
+            timeout_ms: 1_000,
+            deadline_ms: 50,
+            ..Default::default()
+        };
+        let retrying = Retrying::new(Arc::new(Scripted(Mutex::new(vec![None; 3]))), policy);
+        let request = ChatRequest::user(MessageContent::Text("hi".to_string()), 0.1);
+
+        let start = Instant::now();
+        let result = retrying.chat(&request).await;
+        assert!(matches!(result, Err(AgentError::Timeout)));
+        assert!(start.elapsed() < Duration::from_millis(500));
+    }
+}
@@ -0,0 +1,110 @@
+use schemars::{JsonSchema, generate::SchemaSettings};
+use serde::Serialize;
+use serde_json::Value;
+
+// Structured output: the model is constrained to answer with JSON matching
+// `schema`, generated from the type the agent deserializes into
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseFormat {
+    JsonSchema { json_schema: JsonSchemaFormat },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JsonSchemaFormat {
+    pub name: String,
+    pub strict: bool,
+    pub schema: Value,
+}
+
+impl ResponseFormat {
+    pub fn json_schema<T: JsonSchema>(name: &str) -> Self {
+        let generator = SchemaSettings::draft2020_12()
+            .with(|s| {
+                s.inline_subschemas = true;
+                s.meta_schema = None;
+            })
+            .into_generator();
+        let mut schema = generator.into_root_schema_for::<T>().to_value();
+        strict(&mut schema);
+
+        Self::JsonSchema {
+            json_schema: JsonSchemaFormat {
+                name: name.to_string(),
+                strict: true,
+                schema,
+            },
+        }
+    }
+
+    pub fn schema(&self) -> &Value {
+        match self {
+            Self::JsonSchema { json_schema } => &json_schema.schema,
+        }
+    }
+}
+
+// Strict mode wants every property required, no extra properties and none of
+// the annotation keywords schemars adds
+fn strict(schema: &mut Value) {
+    let Value::Object(map) = schema else {
+        if let Value::Array(items) = schema {
+            items.iter_mut().for_each(strict);
+        }
+        return;
+    };
+
+    map.remove("title");
+    map.remove("format");
+    if let Some(Value::Object(properties)) = map.get_mut("properties") {
+        let required = properties.keys().cloned().map(Value::String).collect();
+        properties.values_mut().for_each(strict);
+        map.insert("required".to_string(), Value::Array(required));
+        map.insert("additionalProperties".to_string(), Value::Bool(false));
+    }
+
+    for (key, value) in map.iter_mut() {
+        if key != "properties" {
+            strict(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Answer {
+        title: Option<String>,
+        score: f64,
+        nested: Nested,
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Nested {
+        format: u32,
+    }
+
+    #[test]
+    fn schema_is_strict() {
+        let format = ResponseFormat::json_schema::<Answer>("answer");
+        let schema = format.schema();
+
+        assert_eq!(schema["additionalProperties"], false);
+        assert_eq!(
+            schema["required"],
+            serde_json::json!(["nested", "score", "title"])
+        );
+        assert!(schema.get("title").is_none());
+        assert!(schema["properties"]["score"].get("format").is_none());
+        // Properties that happen to share a keyword's name survive
+        assert!(schema["properties"]["title"].is_object());
+        assert_eq!(
+            schema["properties"]["nested"]["required"],
+            serde_json::json!(["format"])
+        );
+    }
+}
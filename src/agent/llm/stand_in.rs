@@ -0,0 +1,63 @@
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+    task::JoinHandle,
+};
+
+// A one-shot HTTP server answering with a canned response. Resolves to the raw
+// request it received, so tests can check what the provider sent.
+pub async fn serve(
+    status: u16,
+    headers: &[(&str, &str)],
+    body: &str,
+) -> (String, JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let mut response = format!(
+        "HTTP/1.1 {status} Stand-in\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n",
+        body.len()
+    );
+    for (name, value) in headers {
+        response.push_str(&format!("{name}: {value}\r\n"));
+    }
+    response.push_str("\r\n");
+    response.push_str(body);
+
+    let handle = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let request = read_request(&mut socket).await;
+        socket.write_all(response.as_bytes()).await.unwrap();
+        request
+    });
+
+    (url, handle)
+}
+
+async fn read_request(socket: &mut tokio::net::TcpStream) -> String {
+    let mut raw = Vec::new();
+    let mut buf = [0; 4096];
+
+    loop {
+        let n = socket.read(&mut buf).await.unwrap();
+        raw.extend_from_slice(&buf[..n]);
+
+        let text = String::from_utf8_lossy(&raw);
+        if let Some(end) = text.find("\r\n\r\n") {
+            let length = text[..end]
+                .lines()
+                .find_map(|l| {
+                    let (name, value) = l.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length")
+                        .then(|| value.trim().parse::<usize>().ok())?
+                })
+                .unwrap_or(0);
+            if raw.len() >= end + 4 + length {
+                return String::from_utf8_lossy(&raw).into_owned();
+            }
+        }
+        if n == 0 {
+            return String::from_utf8_lossy(&raw).into_owned();
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+
+use super::ResponseFormat;
+use crate::agent::AgentError;
+
+// A chat completion request; the provider supplies the model
+#[derive(Debug, Clone, Serialize)]
+pub struct ChatRequest {
+    pub messages: Vec<Message>,
+    pub temperature: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_logprobs: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<ResponseFormat>,
+}
+
+impl ChatRequest {
+    pub fn user(content: MessageContent, temperature: f64) -> Self {
+        Self {
+            messages: vec![Message {
+                role: "user".to_string(),
+                content,
+            }],
+            temperature,
+            n: None,
+            max_tokens: None,
+            logprobs: None,
+            top_logprobs: None,
+            response_format: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Message {
+    pub role: String,
+    pub content: MessageContent,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum MessageContent {
+    Text(String),
+    Parts(Vec<Content>),
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+pub enum Content {
+    #[serde(rename = "text")]
+    Text { text: String },
+    #[serde(rename = "image_url")]
+    ImageUrl { image_url: ImageUrl },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImageUrl {
+    pub url: String,
+    pub detail: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChatResponse {
+    #[serde(default)]
+    pub choices: Vec<Choice>,
+    pub usage: Option<Usage>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Choice {
+    pub message: ResponseMessage,
+    pub finish_reason: Option<String>,
+    pub logprobs: Option<ChoiceLogprobs>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChoiceLogprobs {
+    pub content: Option<Vec<TokenLogprob>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TokenLogprob {
+    pub token: String,
+    pub logprob: f64,
+    #[serde(default)]
+    pub top_logprobs: Vec<TopLogprob>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TopLogprob {
+    pub token: String,
+    pub logprob: f64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResponseMessage {
+    pub content: Option<String>,
+    pub refusal: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct Usage {
+    pub total_tokens: u32,
+    #[serde(default)]
+    pub completion_tokens: u32,
+}
+
+impl Choice {
+    // The answer text, or why the model gave none
+    pub fn content(&self) -> Result<&str, AgentError> {
+        if let Some(refusal) = &self.message.refusal {
+            return Err(AgentError::Refusal {
+                message: refusal.clone(),
+            });
+        }
+        if self.finish_reason.as_deref() == Some("content_filter") {
+            return Err(AgentError::Refusal {
+                message: "content filtered".to_string(),
+            });
+        }
+
+        self.message
+            .content
+            .as_deref()
+            .ok_or_else(|| AgentError::MalformedOutput {
+                message: "empty answer".to_string(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn choice(json: serde_json::Value) -> Choice {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn refusals_are_told_apart_from_empty_answers() {
+        let refused = choice(serde_json::json!({
+            "message": { "content": null, "refusal": "I can't help with that." },
+            "finish_reason": "stop"
+        }));
+        assert!(matches!(refused.content(), Err(AgentError::Refusal { .. })));
+
+        let filtered = choice(serde_json::json!({
+            "message": { "content": "" },
+            "finish_reason": "content_filter"
+        }));
+        assert!(matches!(
+            filtered.content(),
+            Err(AgentError::Refusal { .. })
+        ));
+
+        let empty = choice(serde_json::json!({ "message": { "content": null } }));
+        assert!(matches!(
+            empty.content(),
+            Err(AgentError::MalformedOutput { .. })
+        ));
+
+        let answered = choice(serde_json::json!({ "message": { "content": "{}" } }));
+        assert_eq!(answered.content(), Ok("{}"));
+    }
+}
@@ -0,0 +1,199 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::core::AgeDistribution;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BirthYearCandidate {
+    pub year: u16,
+    pub plausibility: f64,
+}
+
+// How much a 4-digit number in an email local part should be trusted as a
+// birth year, and what to fall back to for the mass it does not earn.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct BirthYearModel {
+    pub base: f64,
+    pub name_prefix_bonus: f64,
+    pub keyword_penalty: f64,
+    pub keywords: Vec<String>,
+    pub minor_penalty: f64,
+    pub min_age: i32,
+    pub max_age: i32,
+    pub min_plausibility: f64,
+    pub background: [f64; 7],
+}
+
+impl Default for BirthYearModel {
+    fn default() -> Self {
+        Self {
+            base: 0.6,
+            name_prefix_bonus: 0.3,
+            keyword_penalty: 0.5,
+            keywords: [
+                "team", "class", "batch", "grad", "info", "admin", "support", "sales", "office",
+                "hr", "dev", "test", "contact", "events", "club",
+            ]
+            .map(String::from)
+            .to_vec(),
+            minor_penalty: 0.2,
+            min_age: 13,
+            max_age: 80,
+            min_plausibility: 0.5,
+            background: [0.05, 0.15, 0.22, 0.20, 0.16, 0.13, 0.09],
+        }
+    }
+}
+
+impl BirthYearModel {
+    pub fn extract(&self, local_part: &str, current_year: i32) -> Option<BirthYearCandidate> {
+        let re = regex::Regex::new(r"\d+").ok()?;
+
+        re.find_iter(local_part)
+            .filter(|m| m.len() == 4)
+            .filter_map(|m| {
+                let year = m.as_str().parse::<u16>().ok()?;
+                let prefix = &local_part[..m.start()];
+                let plausibility = self.plausibility(prefix, year, current_year);
+
+                (plausibility >= self.min_plausibility)
+                    .then_some(BirthYearCandidate { year, plausibility })
+            })
+            .max_by(|a, b| a.plausibility.total_cmp(&b.plausibility))
+    }
+
+    // Certain when the number earned every bonus and no penalty, so the
+    // threshold follows the configured weights. Anything less only shifts the
+    // age distribution.
+    pub fn is_certain(&self, candidate: &BirthYearCandidate) -> bool {
+        let best = (self.base + self.name_prefix_bonus).clamp(0.0, 1.0);
+        candidate.plausibility >= best - 1e-9
+    }
+
+    pub fn plausibility(&self, prefix: &str, year: u16, current_year: i32) -> f64 {
+        let age = current_year - year as i32;
+        if age < self.min_age || age > self.max_age {
+            return 0.0;
+        }
+
+        // Letters directly in front of the number: "jane" in "jane1990"
+        let word: String = prefix
+            .chars()
+            .rev()
+            .take_while(|c| c.is_alphabetic())
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .flat_map(char::to_lowercase)
+            .collect();
+
+        let mut p = self.base;
+        // Whole words only: "dev" must not penalize "vasudev1990"
+        if self.keywords.contains(&word) {
+            p -= self.keyword_penalty;
+        } else if word.len() >= 2 {
+            p += self.name_prefix_bonus;
+        }
+
+        if age < 18 {
+            p -= self.minor_penalty;
+        }
+
+        p.clamp(0.0, 1.0)
+    }
+
+    pub fn age_distribution(
+        &self,
+        candidate: BirthYearCandidate,
+        today: NaiveDate,
+    ) -> AgeDistribution {
+        let exact = AgeDistribution::from_birth_year(candidate.year, today);
+
+        let trust = candidate.plausibility;
+        let total: f64 = self.background.iter().sum();
+        let mut probs = [0.0; 7];
+        for ((p, e), b) in probs.iter_mut().zip(exact.as_slice()).zip(self.background) {
+            *p = e * trust + (1.0 - trust) * b / total;
+        }
+        AgeDistribution::new(probs).unwrap_or(exact)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::AgeGroup;
+
+    #[test]
+    fn name_prefix_is_more_plausible_than_keyword() {
+        let model = BirthYearModel::default();
+
+        let jane = model.plausibility("jane", 1990, 2026);
+        let team = model.plausibility("team", 1999, 2026);
+        let bare = model.plausibility("", 1990, 2026);
+
+        assert!(jane > bare && bare > team);
+        assert!(model.extract("jane1990", 2026).is_some());
+        assert!(model.extract("team1999", 2026).is_none());
+    }
+
+    #[test]
+    fn keywords_match_whole_words() {
+        let model = BirthYearModel::default();
+
+        let vasudev = model.plausibility("vasudev", 1990, 2026);
+        let dev = model.plausibility("jane.dev", 1990, 2026);
+        assert!(vasudev > dev);
+        assert_eq!(vasudev, model.plausibility("jane", 1990, 2026));
+        assert!(model.extract("shahr1985", 2026).is_some());
+    }
+
+    #[test]
+    fn named_adult_years_are_certain() {
+        let model = BirthYearModel::default();
+        let certain = |local: &str| model.is_certain(&model.extract(local, 2026).unwrap());
+
+        assert!(certain("jane1990"));
+        assert!(!certain("1990"));
+        assert!(!certain("jane2012"));
+    }
+
+    #[test]
+    fn ignores_longer_digit_runs() {
+        let model = BirthYearModel::default();
+        assert!(model.extract("id19901", 2026).is_none());
+    }
+
+    #[test]
+    fn splits_mass_across_boundary() {
+        let model = BirthYearModel::default();
+        let candidate = BirthYearCandidate {
+            year: 2001,
+            plausibility: 1.0,
+        };
+
+        // Turns 25 in 2026: still 24 on Jan 1st
+        let early = model.age_distribution(candidate, NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+        assert!(early.get(AgeGroup::Age18_24) > 0.99);
+
+        let mid = model.age_distribution(candidate, NaiveDate::from_ymd_opt(2026, 7, 2).unwrap());
+        assert!((mid.get(AgeGroup::Age18_24) - 0.5).abs() < 0.01);
+        assert!((mid.get(AgeGroup::Age25_34) - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn holds_back_mass_by_plausibility() {
+        let model = BirthYearModel::default();
+        let candidate = BirthYearCandidate {
+            year: 1990,
+            plausibility: 0.6,
+        };
+
+        let probs =
+            model.age_distribution(candidate, NaiveDate::from_ymd_opt(2026, 12, 31).unwrap());
+        assert!((probs.as_slice().iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(probs.get(AgeGroup::Age35_44) < 0.8);
+        assert!(probs.get(AgeGroup::Age35_44) > 0.6);
+    }
+}
@@ -0,0 +1,208 @@
+pub mod birth_year;
+
+use chrono::Datelike;
+use std::{sync::Arc, time::Instant};
+
+use super::{Agent, AgentError};
+
+pub use birth_year::{BirthYearCandidate, BirthYearModel};
+
+use crate::{
+    core::{
+        AgeEstimate, Attribute, Clock, InferenceInput, InferenceSignal, OrganizationIntelligence,
+        SignalSource,
+    },
+    data::{PERSONAL_EMAIL_DOMAINS, country_for_tld},
+};
+
+// A ccTLD is strong but not conclusive evidence of where the user lives
+const TLD_REGION_PROBABILITY: f64 = 0.8;
+
+pub struct LocalAgent {
+    birth_year: BirthYearModel,
+    clock: Arc<dyn Clock>,
+}
+
+impl LocalAgent {
+    pub fn new(birth_year: BirthYearModel, clock: Arc<dyn Clock>) -> Self {
+        Self { birth_year, clock }
+    }
+
+    fn extract_organization(&self, email: &str) -> Option<OrganizationIntelligence> {
+        let domain = email.split('@').nth(1)?;
+
+        if PERSONAL_EMAIL_DOMAINS.contains(&domain.to_lowercase().as_str()) {
+            return None;
+        }
+
+        Some(OrganizationIntelligence::new(domain))
+    }
+
+    fn extract_region(&self, email: &str) -> Option<&'static str> {
+        country_for_tld(email.split('@').nth(1)?)
+    }
+
+    fn extract_birth_year(&self, email: &str) -> Option<BirthYearCandidate> {
+        let local_part = email.split('@').next()?;
+        self.birth_year
+            .extract(local_part, self.clock.today().year())
+    }
+}
+
+impl Agent for LocalAgent {
+    fn source(&self) -> SignalSource {
+        SignalSource::Local
+    }
+
+    fn attributes(&self) -> &'static [Attribute] {
+        &[Attribute::Organization, Attribute::Region, Attribute::Age]
+    }
+
+    async fn analyze(&self, input: &InferenceInput) -> Result<InferenceSignal, AgentError> {
+        let start = Instant::now();
+        let mut signal = InferenceSignal::new(SignalSource::Local);
+
+        if let Some(email) = &input.email {
+            if let Some(org) = self.extract_organization(email) {
+                signal.explain(
+                    Some(Attribute::Organization),
+                    "org_from_email_domain",
+                    format!("Organization {} extracted from email domain.", org.domain),
+                );
+                signal.organization = Some(org);
+            }
+
+            if let Some(country) = self.extract_region(email) {
+                signal
+                    .region
+                    .insert(country.to_string(), TLD_REGION_PROBABILITY);
+                signal.explain(
+                    Some(Attribute::Region),
+                    "region_from_cctld",
+                    format!("Region {country} suggested by email country-code TLD."),
+                );
+            }
+
+            if let Some(candidate) = self.extract_birth_year(email) {
+                let today = self.clock.today();
+                signal.birth_year = Some(candidate.year);
+                signal.birth_year_certain = self.birth_year.is_certain(&candidate);
+                signal.age = Some(self.birth_year.age_distribution(candidate, today));
+                // An uncertain year gets its estimate from the fused buckets instead
+                if signal.birth_year_certain {
+                    signal.age_estimate = Some(AgeEstimate::from_birth_year(candidate.year, today));
+                }
+
+                signal.explain(
+                    Some(Attribute::Age),
+                    "birth_year_from_email",
+                    format!(
+                        "Birth year {} extracted from email pattern (plausibility {:.2}).",
+                        candidate.year, candidate.plausibility
+                    ),
+                );
+            }
+        }
+
+        signal.latency_ms = start.elapsed().as_millis() as u64;
+        Ok(signal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{FixedClock, SelfIdentified};
+    use chrono::NaiveDate;
+
+    fn agent() -> LocalAgent {
+        let today = NaiveDate::from_ymd_opt(2026, 6, 1).unwrap();
+        LocalAgent::new(BirthYearModel::default(), Arc::new(FixedClock(today)))
+    }
+
+    #[ntex::test]
+    async fn extracts_organization() {
+        let agent = agent();
+        let input = InferenceInput {
+            email: Some("trinity@vogue.com".to_string()),
+            name: None,
+            profile_pic_url: None,
+            browsing_history: None,
+            declared: SelfIdentified::default(),
+        };
+
+        let signal = agent.analyze(&input).await.unwrap();
+        assert_eq!(
+            signal.organization.map(|org| org.domain),
+            Some("vogue.com".to_string())
+        );
+    }
+
+    #[ntex::test]
+    async fn extracts_birth_year() {
+        let agent = agent();
+        let input = InferenceInput {
+            email: Some("laura1992@gmail.com".to_string()),
+            name: None,
+            profile_pic_url: None,
+            browsing_history: None,
+            declared: SelfIdentified::default(),
+        };
+
+        let signal = agent.analyze(&input).await.unwrap();
+        assert_eq!(signal.birth_year, Some(1992));
+
+        // The default model trusts a name followed by an adult birth year
+        let today = NaiveDate::from_ymd_opt(2026, 6, 1).unwrap();
+        assert!(signal.birth_year_certain);
+        assert_eq!(
+            signal.age_estimate,
+            Some(AgeEstimate::from_birth_year(1992, today))
+        );
+    }
+
+    #[ntex::test]
+    async fn ignores_invalid_year() {
+        let agent = agent();
+        let input = InferenceInput {
+            email: Some("test9162@gmail.com".to_string()),
+            name: None,
+            profile_pic_url: None,
+            browsing_history: None,
+            declared: SelfIdentified::default(),
+        };
+
+        let signal = agent.analyze(&input).await.unwrap();
+        assert_eq!(signal.birth_year, None);
+    }
+
+    #[ntex::test]
+    async fn no_birth_year_no_age_probs() {
+        let agent = agent();
+        let input = InferenceInput {
+            email: Some("aparna@gmail.com ".to_string()),
+            name: None,
+            profile_pic_url: None,
+            browsing_history: None,
+            declared: SelfIdentified::default(),
+        };
+
+        let signal = agent.analyze(&input).await.unwrap();
+        assert_eq!(signal.birth_year, None);
+    }
+
+    #[ntex::test]
+    async fn extracts_region_from_cctld() {
+        let agent = agent();
+        let input = InferenceInput {
+            email: Some("oliver@bbc.co.uk".to_string()),
+            name: None,
+            profile_pic_url: None,
+            browsing_history: None,
+            declared: SelfIdentified::default(),
+        };
+
+        let signal = agent.analyze(&input).await.unwrap();
+        assert_eq!(signal.top_region(), Some(("GB", TLD_REGION_PROBABILITY)));
+    }
+}
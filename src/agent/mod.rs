@@ -1,18 +1,32 @@
+pub mod declared;
 pub mod domain;
+pub mod error;
+pub mod llm;
 pub mod local;
 pub mod onomast;
+pub mod sampling;
+pub mod validate;
 pub mod vision;
 
+pub use declared::SelfReportAgent;
 pub use domain::DomainAgent;
+pub use error::AgentError;
 pub use local::*;
-pub use onomast::OnomasticAgent;
+pub use onomast::{OnomasticAgent, Scoring};
+pub use sampling::SamplingConfig;
 pub use vision::VisionAgent;
 
-use crate::core::{InferenceInput, InferenceSignal};
+use crate::core::{Attribute, InferenceInput, InferenceSignal, SignalSource};
 
 pub trait Agent: Send + Sync {
+    fn source(&self) -> SignalSource;
+
+    // What the agent infers; it is skipped when all of them were self-reported
+    fn attributes(&self) -> &'static [Attribute];
+
+    // An empty signal means nothing was found; an error means the agent could not look
     fn analyze(
         &self,
         input: &InferenceInput,
-    ) -> impl std::future::Future<Output = InferenceSignal> + Send;
+    ) -> impl std::future::Future<Output = Result<InferenceSignal, AgentError>> + Send;
 }
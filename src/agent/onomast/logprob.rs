@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+use crate::agent::llm::Choice;
+
+// How the agent turns the model's answer into probabilities
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Scoring {
+    // Probabilities written by the model into its JSON answer
+    #[default]
+    Verbalized,
+    // A lettered multiple-choice question scored from first-token logprobs
+    Logprobs,
+}
+
+pub const TOP_LOGPROBS: u8 = 20;
+
+// Option letters A, B, C... for `count` answers
+pub fn letters(count: usize) -> impl Iterator<Item = char> {
+    ('A'..='Z').take(count)
+}
+
+// Probability mass per option letter in the first generated token, normalized
+// over the options, with the share of the raw mass that landed on them
+pub fn option_probabilities(choice: &Choice, options: usize) -> Option<(Vec<f64>, f64)> {
+    let first = choice.logprobs.as_ref()?.content.as_ref()?.first()?;
+
+    let mut candidates: Vec<(&str, f64)> = first
+        .top_logprobs
+        .iter()
+        .map(|t| (t.token.as_str(), t.logprob))
+        .collect();
+    if candidates.is_empty() {
+        candidates.push((first.token.as_str(), first.logprob));
+    }
+
+    let mut mass = vec![0.0; options];
+    for (token, logprob) in candidates {
+        let token = token.trim().to_uppercase();
+        if let Some(i) = letters(options).position(|l| token == l.to_string()) {
+            mass[i] += logprob.exp();
+        }
+    }
+
+    let coverage: f64 = mass.iter().sum();
+    if coverage <= 0.0 {
+        return None;
+    }
+
+    Some((
+        mass.iter().map(|m| m / coverage).collect(),
+        coverage.min(1.0),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn option_probabilities_from_top_logprobs() {
+        let choice: Choice = serde_json::from_value(serde_json::json!({
+            "message": { "content": "B" },
+            "logprobs": { "content": [{
+                "token": "B",
+                "logprob": -0.22,
+                "top_logprobs": [
+                    { "token": "B", "logprob": -0.22 },
+                    { "token": " a", "logprob": -2.3 },
+                    { "token": "The", "logprob": -4.0 }
+                ]
+            }]}
+        }))
+        .unwrap();
+
+        let (probs, coverage) = option_probabilities(&choice, 2).unwrap();
+        assert!(probs[1] > probs[0]);
+        assert!((probs.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(coverage < 1.0);
+    }
+}
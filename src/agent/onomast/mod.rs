@@ -1,27 +1,42 @@
+mod logprob;
 mod parser;
 mod prompt;
-mod r#type;
 
-use reqwest::Client;
-use std::time::Instant;
+use futures::join;
+use std::{sync::Arc, time::Instant};
 
-use super::Agent;
-use crate::core::{InferenceInput, InferenceSignal, SignalSource};
-use r#type::*;
+use super::{
+    Agent, AgentError,
+    llm::{ChatRequest, ChatResponse, LlmProvider, MessageContent, ResponseFormat},
+    sampling::{self, Sampling},
+};
+use crate::core::{
+    Attribute, Ethnicity, EthnicityDistribution, GenderDistribution, InferenceInput,
+    InferenceSignal, SignalSource,
+};
+
+pub use logprob::Scoring;
 
 pub struct OnomasticAgent {
-    client: Client,
-    api_key: String,
+    llm: Arc<dyn LlmProvider>,
+    sampling: Sampling,
+    scoring: Scoring,
 }
 
 impl OnomasticAgent {
-    pub fn new(api_key: String) -> Self {
+    pub fn new(llm: Arc<dyn LlmProvider>, sampling: Sampling) -> Self {
         Self {
-            client: Client::new(),
-            api_key,
+            llm,
+            sampling,
+            scoring: Scoring::default(),
         }
     }
 
+    pub fn with_scoring(mut self, scoring: Scoring) -> Self {
+        self.scoring = scoring;
+        self
+    }
+
     fn extract_name(&self, input: &InferenceInput) -> Option<String> {
         if let Some(name) = &input.name {
             return Some(name.clone());
@@ -29,8 +44,7 @@ impl OnomasticAgent {
 
         let username = input.email.as_ref()?.split('@').next()?;
         let cleaned: String = username
-            .replace('.', " ")
-            .replace('_', " ")
+            .replace(['.', '_'], " ")
             .chars()
             .filter(|c| c.is_alphabetic() || c.is_whitespace())
             .collect();
@@ -38,85 +52,171 @@ impl OnomasticAgent {
         let name = cleaned.trim().to_string();
         if name.len() >= 2 { Some(name) } else { None }
     }
-}
 
-impl Agent for OnomasticAgent {
-    async fn analyze(&self, input: &InferenceInput) -> InferenceSignal {
-        let start = Instant::now();
-        let mut signal = InferenceSignal::new(SignalSource::Onomastic);
+    fn request(&self, content: String) -> ChatRequest {
+        ChatRequest::user(MessageContent::Text(content), self.sampling.temperature())
+    }
 
-        let name = match self.extract_name(input) {
-            Some(n) => n,
-            None => {
-                signal.latency_ms = start.elapsed().as_millis() as u64;
-                return signal;
-            }
+    async fn complete(&self, request: ChatRequest) -> Result<ChatResponse, AgentError> {
+        self.llm.chat(&request).await
+    }
+
+    async fn verbalized(
+        &self,
+        signal: &mut InferenceSignal,
+        name: &str,
+        email: &str,
+    ) -> Result<(), AgentError> {
+        let format = ResponseFormat::json_schema::<parser::OnomasticResult>("onomastic_result");
+        let request = |n| ChatRequest {
+            n,
+            response_format: Some(format.clone()),
+            ..self.request(prompt::analyze(name, email))
         };
 
-        let request = ChatRequest {
-            model: "gpt-4o-mini".to_string(),
-            messages: vec![Message {
-                role: "user".to_string(),
-                content: prompt::analyze(&name, &input.email.as_deref().unwrap_or("")),
-            }],
-            temperature: 0.1,
+        sampling::sample(
+            self.llm.as_ref(),
+            &self.sampling,
+            request,
+            signal,
+            parser::apply_result,
+        )
+        .await
+    }
+
+    // One single-token multiple-choice question per attribute; the answer
+    // distribution is read off the logprobs rather than the text
+    async fn logprobs(
+        &self,
+        signal: &mut InferenceSignal,
+        name: &str,
+        email: &str,
+    ) -> Result<(), AgentError> {
+        let request = |content| ChatRequest {
+            temperature: 0.0,
+            max_tokens: Some(1),
+            logprobs: Some(true),
+            top_logprobs: Some(logprob::TOP_LOGPROBS),
+            ..self.request(content)
         };
 
-        let response = match self
-            .client
-            .post("https://api.openai.com/v1/chat/completions")
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(&request)
-            .send()
-            .await
-        {
-            Ok(r) => r,
-            Err(e) => {
-                signal
-                    .reasoning
-                    .push(format!("Onomastic request failed: {}", e));
-                signal.latency_ms = start.elapsed().as_millis() as u64;
-                return signal;
-            }
+        let (gender, ethnicity) = join!(
+            self.complete(request(prompt::classify_gender(name, email))),
+            self.complete(request(prompt::classify_ethnicity(name, email)))
+        );
+
+        let (gender, ethnicity) = match (gender, ethnicity) {
+            (Err(e), Err(_)) => return Err(e),
+            (gender, ethnicity) => (gender, ethnicity),
         };
 
-        let chat_response: ChatResponse = match response.json().await {
-            Ok(r) => r,
-            Err(e) => {
-                signal
-                    .reasoning
-                    .push(format!("Failed to parse response: {}", e));
-                signal.latency_ms = start.elapsed().as_millis() as u64;
-                return signal;
+        let mut tokens = None;
+        let mut score =
+            |response: Result<ChatResponse, AgentError>, attribute: Attribute, options: usize| {
+                let chat_response = match response {
+                    Ok(r) => r,
+                    Err(e) => {
+                        signal.fail(e.code(), e.to_string());
+                        return None;
+                    }
+                };
+                if let Some(u) = chat_response.usage {
+                    *tokens.get_or_insert(0) += u.total_tokens;
+                }
+
+                let choices = chat_response.choices;
+                let scored = match choices.first().map(|c| (c, c.content())) {
+                    Some((_, Err(e))) => {
+                        signal.fail(e.code(), e.to_string());
+                        return None;
+                    }
+                    Some((choice, Ok(_))) => logprob::option_probabilities(choice, options),
+                    None => None,
+                };
+                if scored.is_none() {
+                    signal.warn(
+                        Some(attribute),
+                        "missing_logprobs",
+                        "No option letter among the returned logprobs.",
+                    );
+                }
+                scored
+            };
+
+        let gender = score(gender, Attribute::Gender, prompt::GENDER_OPTIONS.len());
+        let ethnicity = score(ethnicity, Attribute::Ethnicity, Ethnicity::ALL.len());
+        signal.tokens_used = tokens;
+
+        if let Some((probs, coverage)) = gender {
+            match GenderDistribution::from_binary(probs[0], probs[1]) {
+                Ok(gender) => signal.gender = Some(gender),
+                Err(e) => signal.warn(
+                    Some(Attribute::Gender),
+                    "invalid_distribution",
+                    format!("Discarded gender probabilities: {e}."),
+                ),
             }
-        };
+            signal.explain(
+                Some(Attribute::Gender),
+                "onomastic_logprobs",
+                format!(
+                    "Gender scored from token logprobs; {:.0}% of the mass fell on the options.",
+                    coverage * 100.0
+                ),
+            );
+        }
 
-        if let Some(error) = chat_response.error {
-            signal
-                .reasoning
-                .push(format!("API error: {}", error.message));
-            signal.latency_ms = start.elapsed().as_millis() as u64;
-            return signal;
+        if let Some((probs, coverage)) = ethnicity {
+            match EthnicityDistribution::from_slice(&probs) {
+                Ok(ethnicity) => signal.ethnicity = Some(ethnicity),
+                Err(e) => signal.warn(
+                    Some(Attribute::Ethnicity),
+                    "invalid_distribution",
+                    format!("Discarded ethnicity probabilities: {e}."),
+                ),
+            }
+            signal.explain(
+                Some(Attribute::Ethnicity),
+                "onomastic_logprobs",
+                format!(
+                    "Ethnicity scored from token logprobs; {:.0}% of the mass fell on the options.",
+                    coverage * 100.0
+                ),
+            );
         }
 
-        signal.tokens_used = chat_response.usage.map(|u| u.total_tokens);
+        Ok(())
+    }
+}
 
-        if let Some(choices) = chat_response.choices {
-            if let Some(choice) = choices.first() {
-                let content = parser::strip_markdown(&choice.message.content);
+impl Agent for OnomasticAgent {
+    fn source(&self) -> SignalSource {
+        SignalSource::Onomastic
+    }
 
-                match serde_json::from_str::<parser::OnomasticResult>(content) {
-                    Ok(result) => parser::apply_result(&mut signal, result),
-                    Err(e) => {
-                        signal
-                            .reasoning
-                            .push(format!("Parse error: {} - Raw: {}", e, content));
-                    }
-                }
+    fn attributes(&self) -> &'static [Attribute] {
+        &[Attribute::Gender, Attribute::Ethnicity]
+    }
+
+    async fn analyze(&self, input: &InferenceInput) -> Result<InferenceSignal, AgentError> {
+        let start = Instant::now();
+        let mut signal = InferenceSignal::new(SignalSource::Onomastic);
+
+        let name = match self.extract_name(input) {
+            Some(n) => n,
+            None => {
+                signal.latency_ms = start.elapsed().as_millis() as u64;
+                return Ok(signal);
             }
+        };
+        let email = input.email.as_deref().unwrap_or("");
+
+        match self.scoring {
+            Scoring::Verbalized => self.verbalized(&mut signal, &name, email).await?,
+            Scoring::Logprobs => self.logprobs(&mut signal, &name, email).await?,
         }
 
         signal.latency_ms = start.elapsed().as_millis() as u64;
-        signal
+        Ok(signal)
     }
 }
@@ -1,35 +1,116 @@
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
 use serde::Deserialize;
-use crate::core::InferenceSignal;
+use std::collections::BTreeMap;
 
-#[derive(Deserialize)]
+use crate::agent::validate;
+use crate::core::{
+    Attribute, Ethnicity, EthnicityDistribution, GenderDistribution, InferenceSignal,
+};
+
+#[derive(Deserialize, JsonSchema)]
 pub struct OnomasticResult {
     pub gender_male: f64,
     pub gender_female: f64,
-    pub ethnicity: Option<String>,
-    pub ethnicity_confidence: f64,
+    #[schemars(schema_with = "ethnicity_schema")]
+    pub ethnicity: Option<BTreeMap<String, f64>>,
     pub reasoning: String,
 }
 
-pub fn strip_markdown(content: &str) -> &str {
-    content
-        .trim()
-        .trim_start_matches("```json")
-        .trim_start_matches("```")
-        .trim_end_matches("```")
-        .trim()
+// One number per known category rather than an open map
+fn ethnicity_schema(_: &mut SchemaGenerator) -> Schema {
+    let properties: serde_json::Map<_, _> = Ethnicity::ALL
+        .iter()
+        .map(|e| {
+            (
+                e.as_str().to_string(),
+                serde_json::json!({ "type": "number" }),
+            )
+        })
+        .collect();
+    json_schema!({ "type": ["object", "null"], "properties": properties })
 }
 
 pub fn apply_result(signal: &mut InferenceSignal, result: OnomasticResult) {
-    if result.gender_male > 0.0 || result.gender_female > 0.0 {
-        signal.gender_male = result.gender_male;
-        signal.gender_female = result.gender_female;
-        signal.gender_other = (1.0 - result.gender_male - result.gender_female).max(0.0);
+    let mut gender = [result.gender_male, result.gender_female];
+    if validate::probabilities(signal, Attribute::Gender, "gender", &mut gender)
+        && gender.iter().any(|p| *p > 0.0)
+    {
+        match GenderDistribution::from_binary(gender[0], gender[1]) {
+            Ok(gender) => signal.gender = Some(gender),
+            Err(e) => signal.warn(
+                Some(Attribute::Gender),
+                "invalid_distribution",
+                format!("Discarded gender probabilities: {e}."),
+            ),
+        }
     }
 
-    if result.ethnicity.is_some() && result.ethnicity_confidence > 0.0 {
-        signal.ethnicity = result.ethnicity;
-        signal.ethnicity_confidence = result.ethnicity_confidence;
+    // Mass on an unknown category cannot be reassigned to the known ones
+    let unknown: Vec<&str> = result
+        .ethnicity
+        .iter()
+        .flat_map(|e| e.keys())
+        .map(String::as_str)
+        .filter(|label| Ethnicity::parse(label).is_none())
+        .collect();
+    if !unknown.is_empty() {
+        validate::reject(
+            signal,
+            Attribute::Ethnicity,
+            format!("unknown ethnicity categories {}", unknown.join(", ")),
+        );
+    } else if let Some(ethnicity) = result.ethnicity {
+        let mut weights = Ethnicity::ALL.map(|e| ethnicity.get(e.as_str()).copied().unwrap_or(0.0));
+        if validate::probabilities(signal, Attribute::Ethnicity, "ethnicity", &mut weights)
+            && weights.iter().any(|p| *p > 0.0)
+        {
+            let labels = Ethnicity::ALL
+                .map(Ethnicity::as_str)
+                .into_iter()
+                .zip(weights);
+            match EthnicityDistribution::from_labels(labels) {
+                Ok(ethnicity) => signal.ethnicity = Some(ethnicity),
+                Err(e) => signal.warn(
+                    Some(Attribute::Ethnicity),
+                    "invalid_distribution",
+                    format!("Discarded ethnicity probabilities: {e}."),
+                ),
+            }
+        }
     }
 
-    signal.reasoning.push(result.reasoning);
+    let covered: Vec<_> = [
+        signal.gender.map(|_| Attribute::Gender),
+        signal.ethnicity.map(|_| Attribute::Ethnicity),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    signal.explain_each(&covered, "onomastic_analysis", result.reasoning);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::SignalSource;
+
+    #[test]
+    fn unknown_ethnicity_rejects_the_whole_distribution() {
+        let result: OnomasticResult = serde_json::from_str(
+            r#"{"gender_male": 0.1, "gender_female": 0.9,
+                "ethnicity": {"european": 0.3, "martian": 0.7}, "reasoning": "..."}"#,
+        )
+        .unwrap();
+
+        let mut signal = InferenceSignal::new(SignalSource::Onomastic);
+        apply_result(&mut signal, result);
+
+        assert!(signal.gender.is_some());
+        assert!(signal.ethnicity.is_none());
+        let warning = signal
+            .reasoning
+            .iter()
+            .find(|e| e.code == "output_rejected");
+        assert!(warning.unwrap().message.contains("martian"));
+    }
 }
@@ -1,4 +1,14 @@
+use crate::core::Ethnicity;
+
+use super::logprob::letters;
+
+pub const GENDER_OPTIONS: [&str; 2] = ["male", "female"];
+
 pub fn analyze(name: &str, email: &str) -> String {
+    let ethnicity = Ethnicity::ALL
+        .map(|e| format!("\"{}\": 0.0", e.as_str()))
+        .join(", ");
+
     format!(
         r#"Analyze the name and email to infer gender and ethnicity/cultural background.
 
@@ -6,7 +16,46 @@ Name: {}
 Email: {}
 
 Return ONLY JSON, no markdown:
-{{"gender_male": 0.0, "gender_female": 0.0, "ethnicity": "south_asian"|"east_asian"|"southeast_asian"|"european"|"african"|"latin_american"|"middle_eastern"|null, "ethnicity_confidence": 0.0, "reasoning": "..."}}"#,
-        name, email
+{{"gender_male": 0.0, "gender_female": 0.0, "ethnicity": {{{}}}|null, "reasoning": "..."}}
+
+"ethnicity" holds a probability for each listed category, summing to 1, or is null if you cannot tell. Use only these keys."#,
+        name, email, ethnicity
+    )
+}
+
+pub fn classify_gender(name: &str, email: &str) -> String {
+    classify(name, email, "most likely gender", &GENDER_OPTIONS)
+}
+
+pub fn classify_ethnicity(name: &str, email: &str) -> String {
+    let options = Ethnicity::ALL.map(|e| e.as_str().replace('_', " "));
+    let options: Vec<&str> = options.iter().map(String::as_str).collect();
+    classify(
+        name,
+        email,
+        "most likely ethnicity/cultural background",
+        &options,
+    )
+}
+
+fn classify(name: &str, email: &str, question: &str, options: &[&str]) -> String {
+    let options: Vec<String> = letters(options.len())
+        .zip(options)
+        .map(|(letter, option)| format!("{letter}) {option}"))
+        .collect();
+
+    format!(
+        r#"Given the name and email, what is the person's {}?
+
+Name: {}
+Email: {}
+
+{}
+
+Answer with the letter only."#,
+        question,
+        name,
+        email,
+        options.join("\n")
     )
 }
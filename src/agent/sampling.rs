@@ -0,0 +1,349 @@
+use futures::future::join_all;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use super::{
+    AgentError,
+    llm::{ChatRequest, LlmProvider, strip_markdown},
+};
+use crate::core::{
+    InferenceSignal, SamplingMetrics, distribution::Distribution, reasoning::EvidenceKind,
+};
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SampleMode {
+    // One request asking for `n` choices
+    #[default]
+    Choices,
+    // Independent requests sent concurrently
+    Calls,
+}
+
+// Self-consistency: ask the same question several times and trust the
+// answers only as far as they agree
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Sampling {
+    pub samples: u32,
+    pub mode: SampleMode,
+    pub temperature: f64,
+}
+
+impl Default for Sampling {
+    fn default() -> Self {
+        Self {
+            samples: 1,
+            mode: SampleMode::Choices,
+            temperature: 0.7,
+        }
+    }
+}
+
+impl Sampling {
+    pub fn is_enabled(&self) -> bool {
+        self.samples > 1
+    }
+
+    // A single answer keeps the near-deterministic temperature
+    pub fn temperature(&self) -> f64 {
+        if self.is_enabled() {
+            self.temperature
+        } else {
+            0.1
+        }
+    }
+
+    // Tokens beyond what a single sample would have cost, from the
+    // (total, completion) usage of each response
+    pub fn extra_tokens(&self, usage: &[(u32, u32)]) -> u32 {
+        match self.mode {
+            _ if !self.is_enabled() => 0,
+            SampleMode::Calls => usage.iter().skip(1).map(|(total, _)| total).sum(),
+            SampleMode::Choices => usage
+                .iter()
+                .map(|(_, completion)| completion - completion / self.samples)
+                .sum(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SamplingConfig {
+    pub onomastic: Sampling,
+    pub vision: Sampling,
+}
+
+// Asks the question as many times as configured, parses every answer as `T`
+// and folds the samples into `signal` through `apply`. Fails only when no
+// sample at all could be read.
+pub async fn sample<T: DeserializeOwned>(
+    llm: &dyn LlmProvider,
+    sampling: &Sampling,
+    request: impl Fn(Option<u32>) -> ChatRequest,
+    signal: &mut InferenceSignal,
+    apply: impl Fn(&mut InferenceSignal, T),
+) -> Result<(), AgentError> {
+    let complete = |n| {
+        let request = request(n);
+        async move { llm.chat(&request).await }
+    };
+    let responses = match sampling.mode {
+        _ if !sampling.is_enabled() => vec![complete(None).await],
+        SampleMode::Choices => vec![complete(Some(sampling.samples)).await],
+        SampleMode::Calls => join_all((0..sampling.samples).map(|_| complete(None))).await,
+    };
+
+    let mut samples = Vec::new();
+    let mut usage = Vec::new();
+    let mut errors = Vec::new();
+    for response in responses {
+        let chat_response = match response {
+            Ok(r) => r,
+            Err(e) => {
+                errors.push(e);
+                continue;
+            }
+        };
+
+        if let Some(u) = chat_response.usage {
+            usage.push((u.total_tokens, u.completion_tokens));
+        }
+
+        for choice in chat_response.choices {
+            let parsed = choice.content().and_then(|content| {
+                serde_json::from_str::<T>(strip_markdown(content)).map_err(|e| {
+                    AgentError::MalformedOutput {
+                        message: e.to_string(),
+                    }
+                })
+            });
+
+            match parsed {
+                Ok(result) => {
+                    let mut sample = InferenceSignal::new(signal.source.clone());
+                    apply(&mut sample, result);
+                    samples.push(sample);
+                }
+                Err(e) => errors.push(e),
+            }
+        }
+    }
+
+    if samples.is_empty()
+        && let Some(error) = errors.first()
+    {
+        return Err(error.clone());
+    }
+    for error in errors {
+        signal.fail(error.code(), error.to_string());
+    }
+
+    if !usage.is_empty() {
+        signal.tokens_used = Some(usage.iter().map(|(total, _)| total).sum());
+    }
+    let extra_tokens = sampling.extra_tokens(&usage);
+    aggregate(signal, samples, sampling, extra_tokens);
+    Ok(())
+}
+
+// Folds parsed samples into `signal`. Distributions are averaged and their
+// divergence from the average is recorded as the source's spread.
+pub fn aggregate(
+    signal: &mut InferenceSignal,
+    samples: Vec<InferenceSignal>,
+    sampling: &Sampling,
+    extra_tokens: u32,
+) {
+    let Some(first) = samples.first() else {
+        return;
+    };
+
+    signal.gender = mean(samples.iter().filter_map(|s| s.gender.as_ref()));
+    signal.age = mean(samples.iter().filter_map(|s| s.age.as_ref()));
+    signal.ethnicity = mean(samples.iter().filter_map(|s| s.ethnicity.as_ref()));
+
+    let votes: Vec<bool> = samples.iter().filter_map(|s| s.is_human).collect();
+    if !votes.is_empty() {
+        signal.is_human = Some(votes.iter().filter(|h| **h).count() * 2 >= votes.len());
+    }
+    if signal.is_human == Some(false) {
+        signal.gender = None;
+        signal.age = None;
+    }
+
+    // The first answer explains; later ones only add warnings it lacks
+    signal.reasoning.extend(first.reasoning.iter().cloned());
+    for entry in samples.iter().skip(1).flat_map(|s| &s.reasoning) {
+        let seen = signal.reasoning.iter().any(|e| e.code == entry.code);
+        if entry.kind != EvidenceKind::Evidence && !seen {
+            signal.reasoning.push(entry.clone());
+        }
+    }
+
+    if !sampling.is_enabled() {
+        return;
+    }
+
+    let spreads: Vec<f64> = [
+        divergence(
+            samples.iter().map(|s| s.gender.as_ref()),
+            signal.gender.as_ref(),
+        ),
+        divergence(samples.iter().map(|s| s.age.as_ref()), signal.age.as_ref()),
+        divergence(
+            samples.iter().map(|s| s.ethnicity.as_ref()),
+            signal.ethnicity.as_ref(),
+        ),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    let spread = if spreads.is_empty() {
+        0.0
+    } else {
+        spreads.iter().sum::<f64>() / spreads.len() as f64
+    };
+
+    signal.sampling = Some(SamplingMetrics {
+        requested: sampling.samples,
+        parsed: samples.len() as u32,
+        spread,
+        extra_tokens,
+    });
+    signal.explain(
+        None,
+        "self_consistency",
+        format!(
+            "Aggregated {} of {} samples with spread {spread:.2}.",
+            samples.len(),
+            sampling.samples
+        ),
+    );
+}
+
+fn mean<'a, const N: usize, K: 'a>(
+    dists: impl Iterator<Item = &'a Distribution<N, K>>,
+) -> Option<Distribution<N, K>> {
+    let mut sum = [0.0; N];
+    for dist in dists {
+        for (s, p) in sum.iter_mut().zip(dist.as_slice()) {
+            *s += p;
+        }
+    }
+    Distribution::new(sum).ok()
+}
+
+// Mean Jensen-Shannon divergence of the samples that carried the attribute
+fn divergence<'a, const N: usize, K: 'a>(
+    samples: impl Iterator<Item = Option<&'a Distribution<N, K>>>,
+    aggregate: Option<&Distribution<N, K>>,
+) -> Option<f64> {
+    let aggregate = aggregate?;
+    let divergences: Vec<f64> = samples.flatten().map(|d| d.js(aggregate)).collect();
+    if divergences.is_empty() {
+        return None;
+    }
+    Some(divergences.iter().sum::<f64>() / divergences.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::future::BoxFuture;
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::{
+        agent::llm::{ChatResponse, MessageContent},
+        core::{GenderDistribution, SignalSource},
+    };
+
+    // Answers each call with the next scripted content
+    #[derive(Debug)]
+    struct Scripted(Mutex<Vec<&'static str>>);
+
+    impl LlmProvider for Scripted {
+        fn chat<'a>(
+            &'a self,
+            _: &'a ChatRequest,
+        ) -> BoxFuture<'a, Result<ChatResponse, AgentError>> {
+            let content = self.0.lock().unwrap().remove(0);
+            let choices = serde_json::json!([{ "message": { "content": content } }]);
+            let response = serde_json::from_value(serde_json::json!({ "choices": choices }));
+            Box::pin(async move { Ok(response.unwrap()) })
+        }
+    }
+
+    fn sample(male: f64) -> InferenceSignal {
+        let mut signal = InferenceSignal::new(SignalSource::Onomastic);
+        signal.gender = Some(GenderDistribution::from_binary(male, 1.0 - male).unwrap());
+        signal
+    }
+
+    #[test]
+    fn disagreeing_samples_average_and_spread() {
+        let sampling = Sampling {
+            samples: 2,
+            ..Sampling::default()
+        };
+
+        let mut agreeing = InferenceSignal::new(SignalSource::Onomastic);
+        aggregate(&mut agreeing, vec![sample(0.9), sample(0.9)], &sampling, 0);
+        let mut split = InferenceSignal::new(SignalSource::Onomastic);
+        aggregate(&mut split, vec![sample(1.0), sample(0.0)], &sampling, 0);
+
+        assert!((split.gender.unwrap().male() - 0.5).abs() < 1e-9);
+        let spread = |s: &InferenceSignal| s.sampling.as_ref().unwrap().spread;
+        assert!(spread(&agreeing) < 1e-9);
+        assert!(spread(&split) > 0.3);
+    }
+
+    #[test]
+    fn extra_tokens_by_mode() {
+        let choices = Sampling {
+            samples: 4,
+            ..Sampling::default()
+        };
+        let calls = Sampling {
+            mode: SampleMode::Calls,
+            ..choices.clone()
+        };
+
+        assert_eq!(choices.extra_tokens(&[(500, 400)]), 300);
+        assert_eq!(calls.extra_tokens(&[(200, 100); 4]), 600);
+        assert_eq!(Sampling::default().extra_tokens(&[(200, 100)]), 0);
+    }
+
+    #[ntex::test]
+    async fn unreadable_samples_are_reported_not_fatal() {
+        let sampling = Sampling {
+            samples: 3,
+            mode: SampleMode::Calls,
+            ..Sampling::default()
+        };
+        let llm = Scripted(Mutex::new(vec!["0.8", "not json", "```\n0.6\n```"]));
+        let request = |_| ChatRequest::user(MessageContent::Text("hi".to_string()), 0.7);
+        let apply = |signal: &mut InferenceSignal, male: f64| {
+            signal.gender = GenderDistribution::from_binary(male, 1.0 - male).ok();
+        };
+
+        let mut signal = InferenceSignal::new(SignalSource::Vision);
+        super::sample(&llm, &sampling, request, &mut signal, apply)
+            .await
+            .unwrap();
+
+        assert!((signal.gender.unwrap().male() - 0.7).abs() < 1e-9);
+        assert_eq!(signal.sampling.unwrap().parsed, 2);
+        assert!(
+            signal
+                .reasoning
+                .iter()
+                .any(|e| e.code == "malformed_output")
+        );
+
+        let llm = Scripted(Mutex::new(vec!["not json"]));
+        let mut signal = InferenceSignal::new(SignalSource::Vision);
+        let result = super::sample(&llm, &Sampling::default(), request, &mut signal, apply).await;
+        assert!(matches!(result, Err(AgentError::MalformedOutput { .. })));
+    }
+}
@@ -0,0 +1,103 @@
+use crate::core::{Attribute, InferenceSignal};
+
+const TOLERANCE: f64 = 1e-6;
+
+// Checks LLM probabilities before they become a distribution. Values outside
+// [0, 1] are clamped and a total above 1 is scaled down; both are reported as
+// repairs. Non-finite values cannot be repaired, so the attribute is rejected.
+pub fn probabilities(
+    signal: &mut InferenceSignal,
+    attribute: Attribute,
+    label: &str,
+    values: &mut [f64],
+) -> bool {
+    if values.iter().any(|p| !p.is_finite()) {
+        reject(
+            signal,
+            attribute,
+            format!("{label} probabilities are not finite"),
+        );
+        return false;
+    }
+
+    let mut repairs = Vec::new();
+    let clamped = values.iter().filter(|p| !(0.0..=1.0).contains(*p)).count();
+    if clamped > 0 {
+        values.iter_mut().for_each(|p| *p = p.clamp(0.0, 1.0));
+        repairs.push(format!("clamped {clamped} value(s) into [0, 1]"));
+    }
+
+    let total: f64 = values.iter().sum();
+    if total > 1.0 + TOLERANCE {
+        values.iter_mut().for_each(|p| *p /= total);
+        repairs.push(format!("renormalized from a total of {total:.2}"));
+    }
+
+    if !repairs.is_empty() {
+        repair(
+            signal,
+            attribute,
+            format!("{label} probabilities {}", repairs.join(" and ")),
+        );
+    }
+    true
+}
+
+pub fn repair(signal: &mut InferenceSignal, attribute: Attribute, change: String) {
+    signal.warn(
+        Some(attribute),
+        "output_repaired",
+        format!("Repaired model output: {change}."),
+    );
+}
+
+pub fn reject(signal: &mut InferenceSignal, attribute: Attribute, reason: String) {
+    signal.warn(
+        Some(attribute),
+        "output_rejected",
+        format!("Rejected model output: {reason}."),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::SignalSource;
+
+    #[test]
+    fn out_of_range_probabilities_are_repaired() {
+        let mut signal = InferenceSignal::new(SignalSource::Vision);
+
+        let mut gender = [-0.2, 0.9];
+        assert!(probabilities(
+            &mut signal,
+            Attribute::Gender,
+            "gender",
+            &mut gender
+        ));
+        assert_eq!(gender, [0.0, 0.9]);
+
+        let mut gender = [0.8, 0.6];
+        assert!(probabilities(
+            &mut signal,
+            Attribute::Gender,
+            "gender",
+            &mut gender
+        ));
+        assert!((gender[0] - 0.8 / 1.4).abs() < 1e-9);
+
+        let mut gender = [f64::NAN, 0.5];
+        assert!(!probabilities(
+            &mut signal,
+            Attribute::Gender,
+            "gender",
+            &mut gender
+        ));
+
+        let codes: Vec<_> = signal.reasoning.iter().map(|r| r.code.as_str()).collect();
+        assert_eq!(
+            codes,
+            ["output_repaired", "output_repaired", "output_rejected"]
+        );
+    }
+}
@@ -1,30 +1,36 @@
 mod parser;
 mod prompt;
-mod r#type;
 
-use reqwest::Client;
-use std::time::Instant;
+use std::{sync::Arc, time::Instant};
 
-use super::Agent;
-use crate::core::{InferenceInput, InferenceSignal, SignalSource};
-use r#type::*;
+use super::{
+    Agent, AgentError,
+    llm::{ChatRequest, Content, ImageUrl, LlmProvider, MessageContent, ResponseFormat},
+    sampling::{self, Sampling},
+};
+use crate::core::{Attribute, InferenceInput, InferenceSignal, SignalSource};
 
 pub struct VisionAgent {
-    client: Client,
-    api_key: String,
+    llm: Arc<dyn LlmProvider>,
+    sampling: Sampling,
 }
 
 impl VisionAgent {
-    pub fn new(api_key: String) -> Self {
-        Self {
-            client: Client::new(),
-            api_key,
-        }
+    pub fn new(llm: Arc<dyn LlmProvider>, sampling: Sampling) -> Self {
+        Self { llm, sampling }
     }
 }
 
 impl Agent for VisionAgent {
-    async fn analyze(&self, input: &InferenceInput) -> InferenceSignal {
+    fn source(&self) -> SignalSource {
+        SignalSource::Vision
+    }
+
+    fn attributes(&self) -> &'static [Attribute] {
+        &[Attribute::Gender, Attribute::Age]
+    }
+
+    async fn analyze(&self, input: &InferenceInput) -> Result<InferenceSignal, AgentError> {
         let start = Instant::now();
         let mut signal = InferenceSignal::new(SignalSource::Vision);
 
@@ -32,84 +38,38 @@ impl Agent for VisionAgent {
             Some(url) => url,
             None => {
                 signal.latency_ms = start.elapsed().as_millis() as u64;
-                return signal;
-            }
-        };
-
-        let request = ChatRequest {
-            model: "gpt-4o-mini".to_string(),
-            messages: vec![Message {
-                role: "user".to_string(),
-                content: vec![
-                    Content::Text {
-                        text: prompt::analyze_image(),
-                    },
-                    Content::ImageUrl {
-                        image_url: ImageUrl {
-                            url: image_url.clone(),
-                            detail: "low".to_string(),
-                        },
-                    },
-                ],
-            }],
-            temperature: 0.1,
-        };
-
-        let response = match self
-            .client
-            .post("https://api.openai.com/v1/chat/completions")
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(&request)
-            .send()
-            .await
-        {
-            Ok(r) => r,
-            Err(e) => {
-                signal
-                    .reasoning
-                    .push(format!("Vision request failed: {}", e));
-                signal.latency_ms = start.elapsed().as_millis() as u64;
-                return signal;
+                return Ok(signal);
             }
         };
 
-        let chat_response: ChatResponse = match response.json().await {
-            Ok(r) => r,
-            Err(e) => {
-                signal
-                    .reasoning
-                    .push(format!("Failed to parse response: {}", e));
-                signal.latency_ms = start.elapsed().as_millis() as u64;
-                return signal;
-            }
+        let content = MessageContent::Parts(vec![
+            Content::Text {
+                text: prompt::analyze_image(),
+            },
+            Content::ImageUrl {
+                image_url: ImageUrl {
+                    url: image_url.clone(),
+                    detail: "low".to_string(),
+                },
+            },
+        ]);
+        let format = ResponseFormat::json_schema::<parser::VisionResult>("vision_result");
+        let request = |n| ChatRequest {
+            n,
+            response_format: Some(format.clone()),
+            ..ChatRequest::user(content.clone(), self.sampling.temperature())
         };
 
-        if let Some(error) = chat_response.error {
-            signal
-                .reasoning
-                .push(format!("API error: {}", error.message));
-            signal.latency_ms = start.elapsed().as_millis() as u64;
-            return signal;
-        }
-
-        signal.tokens_used = chat_response.usage.map(|u| u.total_tokens);
-
-        if let Some(choices) = chat_response.choices {
-            if let Some(choice) = choices.first() {
-                let content = parser::strip_markdown(&choice.message.content);
-
-                match serde_json::from_str::<parser::VisionResult>(content) {
-                    Ok(result) => parser::apply_result(&mut signal, result),
-                    Err(e) => {
-                        signal
-                            .reasoning
-                            .push(format!("Parse error: {} - Raw: {}", e, content));
-                    }
-                }
-            }
-        }
+        sampling::sample(
+            self.llm.as_ref(),
+            &self.sampling,
+            request,
+            &mut signal,
+            parser::apply_result,
+        )
+        .await?;
 
         signal.latency_ms = start.elapsed().as_millis() as u64;
-        signal
+        Ok(signal)
     }
 }
@@ -1,57 +1,66 @@
-use crate::core::{AgeGroup, InferenceSignal};
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
 use serde::Deserialize;
 
-#[derive(Deserialize)]
+use crate::agent::validate;
+use crate::core::{AgeDistribution, AgeGroup, Attribute, GenderDistribution, InferenceSignal};
+
+#[derive(Deserialize, JsonSchema)]
 pub struct VisionResult {
     pub gender_male: f64,
     pub gender_female: f64,
+    #[schemars(schema_with = "age_group_schema")]
     pub age_group: Option<String>,
+    #[allow(dead_code)]
     pub age_confidence: f64,
     pub is_human: bool,
     pub reasoning: String,
 }
 
-pub fn strip_markdown(content: &str) -> &str {
-    content
-        .trim()
-        .trim_start_matches("```json")
-        .trim_start_matches("```")
-        .trim_end_matches("```")
-        .trim()
-}
-
-pub fn parse_age_group(s: &str) -> Option<AgeGroup> {
-    match s {
-        "under_18" => Some(AgeGroup::Under18),
-        "18-24" => Some(AgeGroup::Age18_24),
-        "25-34" => Some(AgeGroup::Age25_34),
-        "35-44" => Some(AgeGroup::Age35_44),
-        "45-54" => Some(AgeGroup::Age45_54),
-        "55-64" => Some(AgeGroup::Age55_64),
-        "65+" => Some(AgeGroup::Age65Plus),
-        _ => None,
-    }
+fn age_group_schema(_: &mut SchemaGenerator) -> Schema {
+    let mut labels: Vec<_> = AgeGroup::ALL.iter().map(|g| Some(g.label())).collect();
+    labels.push(None);
+    json_schema!({ "type": ["string", "null"], "enum": labels })
 }
 
 pub fn apply_result(signal: &mut InferenceSignal, result: VisionResult) {
+    signal.is_human = Some(result.is_human);
+
     if result.is_human {
         // Only set gender if Vision actually detected something
-        if result.gender_male > 0.0 || result.gender_female > 0.0 {
-            signal.gender_male = result.gender_male;
-            signal.gender_female = result.gender_female;
-            signal.gender_other = (1.0 - result.gender_male - result.gender_female).max(0.0);
+        let mut gender = [result.gender_male, result.gender_female];
+        if validate::probabilities(signal, Attribute::Gender, "gender", &mut gender)
+            && gender.iter().any(|p| *p > 0.0)
+        {
+            match GenderDistribution::from_binary(gender[0], gender[1]) {
+                Ok(gender) => signal.gender = Some(gender),
+                Err(e) => signal.warn(
+                    Some(Attribute::Gender),
+                    "invalid_distribution",
+                    format!("Discarded gender probabilities: {e}."),
+                ),
+            }
         }
 
-        if let Some(age_str) = &result.age_group {
-            if let Some(age_group) = parse_age_group(age_str) {
-                signal.set_age_probs(age_group.to_one_hot());
+        if let Some(label) = &result.age_group {
+            match AgeGroup::parse(label) {
+                Some(group) => signal.age = Some(AgeDistribution::from_group(group)),
+                None => validate::reject(
+                    signal,
+                    Attribute::Age,
+                    format!("unknown age group {label:?}"),
+                ),
             }
         }
 
-        return signal.reasoning.push(result.reasoning);
+        let covered: Vec<_> = [
+            signal.gender.map(|_| Attribute::Gender),
+            signal.age.map(|_| Attribute::Age),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        return signal.explain_each(&covered, "vision_analysis", result.reasoning);
     }
 
-    return signal
-        .reasoning
-        .push("Profile picture is not human.".to_string());
+    signal.warn(None, "non_human_avatar", "Profile picture is not human.");
 }
@@ -1,7 +1,13 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::edge::EdgeCase;
 
 use crate::core::{
-    AgeGroup, Confidence, Gender, InferenceMetrics, InferenceSignal, OrganizationIntelligence,
+    Abstention, AgeEstimate, AgeGroup, Attribute, Confidence, ConfidencePolicy, Ethnicity, Gender,
+    GenderDistribution, InferenceMetrics, InferenceSignal, OrganizationIntelligence,
+    ReasoningEntry, SchemedAge, StrategyKind, reasoning::render,
 };
 
 #[derive(Debug, Deserialize)]
@@ -10,6 +16,9 @@ pub struct AnalyzeRequest {
     pub name: Option<String>,
     pub profile_pic_url: Option<String>,
     pub browsing_history: Option<Vec<String>>,
+    pub pronouns: Option<String>,
+    pub gender_identity: Option<String>,
+    pub birth_year: Option<u16>,
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -25,6 +34,10 @@ pub struct QueryParams {
     pub minimal: Option<bool>,
     #[serde(default)]
     pub format: Format,
+    pub strategy: Option<StrategyKind>,
+    pub tenant: Option<String>,
+    pub age_scheme: Option<String>,
+    pub as_of: Option<NaiveDate>,
 }
 
 #[derive(Debug, Serialize)]
@@ -33,8 +46,9 @@ pub struct InferResponse {
     pub gender_female: f64,
     pub gender_other: f64,
 
-    pub ethnicity: Option<String>,
+    pub ethnicity: Option<Ethnicity>,
     pub ethnicity_confidence: f64,
+    pub ethnicity_distribution: BTreeMap<Ethnicity, f64>,
 
     pub age_group_under_18: f64,
     pub age_group_18_24: f64,
@@ -44,16 +58,44 @@ pub struct InferResponse {
     pub age_group_55_64: f64,
     pub age_group_65_plus: f64,
 
+    pub age_estimate: Option<AgeEstimate>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age_scheme: Option<SchemedAge>,
+
+    pub region: BTreeMap<String, f64>,
+
     pub birth_year: Option<u16>,
     pub organization: Option<OrganizationIntelligence>,
 
+    pub self_reported: Vec<Attribute>,
+
     pub reasoning: Vec<String>,
+    pub explanations: Vec<ReasoningEntry>,
+    pub edge_case: EdgeCase,
+    // Demographics withheld because the person is likely a minor
+    pub restricted: bool,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metrics: Option<InferenceMetrics>,
 }
 
 impl InferResponse {
+    pub fn with_age_scheme(mut self, age_scheme: Option<SchemedAge>) -> Self {
+        self.age_scheme = age_scheme;
+        self
+    }
+
+    pub fn with_restricted(mut self, restricted: bool) -> Self {
+        self.restricted = restricted;
+        self
+    }
+
+    pub fn with_edge_case(mut self, edge_case: EdgeCase) -> Self {
+        self.edge_case = edge_case;
+        self
+    }
+
     pub fn with_metrics_if<F>(mut self, condition: bool, f: F) -> Self
     where
         F: FnOnce() -> InferenceMetrics,
@@ -67,26 +109,43 @@ impl InferResponse {
 
 impl From<InferenceSignal> for InferResponse {
     fn from(s: InferenceSignal) -> Self {
-        Self {
-            gender_male: s.gender_male,
-            gender_female: s.gender_female,
-            gender_other: s.gender_other,
-
-            ethnicity: s.ethnicity,
-            ethnicity_confidence: s.ethnicity_confidence,
+        // Flat fields keep the original wire format; no evidence serializes as zeros
+        let gender = |f: fn(&GenderDistribution) -> f64| s.gender.as_ref().map_or(0.0, f);
+        let age = |group| s.age.map_or(0.0, |d| d.get(group));
+        let (ethnicity, ethnicity_confidence) = s.ethnicity.map(|d| d.most_likely()).unzip();
 
-            age_group_under_18: s.age_group_under_18,
-            age_group_18_24: s.age_group_18_24,
-            age_group_25_34: s.age_group_25_34,
-            age_group_35_44: s.age_group_35_44,
-            age_group_45_54: s.age_group_45_54,
-            age_group_55_64: s.age_group_55_64,
-            age_group_65_plus: s.age_group_65_plus,
+        Self {
+            gender_male: gender(GenderDistribution::male),
+            gender_female: gender(GenderDistribution::female),
+            gender_other: gender(GenderDistribution::other),
+
+            ethnicity,
+            ethnicity_confidence: ethnicity_confidence.unwrap_or(0.0),
+            ethnicity_distribution: s
+                .ethnicity
+                .map(|d| Ethnicity::ALL.into_iter().map(|e| (e, d.get(e))).collect())
+                .unwrap_or_default(),
+
+            age_group_under_18: age(AgeGroup::Under18),
+            age_group_18_24: age(AgeGroup::Age18_24),
+            age_group_25_34: age(AgeGroup::Age25_34),
+            age_group_35_44: age(AgeGroup::Age35_44),
+            age_group_45_54: age(AgeGroup::Age45_54),
+            age_group_55_64: age(AgeGroup::Age55_64),
+            age_group_65_plus: age(AgeGroup::Age65Plus),
+            age_estimate: s.age_estimate,
+            age_scheme: None,
+
+            region: s.region,
 
             birth_year: s.birth_year,
             organization: s.organization,
+            self_reported: s.self_reported,
 
-            reasoning: s.reasoning,
+            reasoning: render(&s.reasoning),
+            explanations: s.reasoning,
+            edge_case: EdgeCase::default(),
+            restricted: false,
             metrics: None,
         }
     }
@@ -97,40 +156,81 @@ pub struct FuzzyResponse {
     pub gender: Gender,
     pub gender_confidence: Confidence,
 
-    pub ethnicity: Option<String>,
+    pub ethnicity: Option<Ethnicity>,
     pub ethnicity_confidence: Confidence,
 
-    pub age_group: Option<AgeGroup>,
+    pub age_group: Option<String>,
     pub age_group_confidence: Confidence,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age_scheme: Option<String>,
+    pub age_estimate: Option<AgeEstimate>,
+
+    pub region: Option<String>,
+    pub region_confidence: Confidence,
+
+    pub abstentions: Vec<Abstention>,
 
     pub organization: Option<OrganizationIntelligence>,
 
+    pub self_reported: Vec<Attribute>,
+
     pub reasoning: Vec<String>,
+    pub explanations: Vec<ReasoningEntry>,
+    pub edge_case: EdgeCase,
+    // Demographics withheld because the person is likely a minor
+    pub restricted: bool,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metrics: Option<InferenceMetrics>,
 }
 
-impl From<InferenceSignal> for FuzzyResponse {
-    fn from(s: InferenceSignal) -> Self {
-        let (gender, gender_confidence) = resolve_gender(&s);
-        let (age_group, age_group_confidence) = resolve_age_group(&s);
+impl FuzzyResponse {
+    pub fn new(
+        s: InferenceSignal,
+        policy: &ConfidencePolicy,
+        age_scheme: Option<&SchemedAge>,
+    ) -> Self {
+        let mut abstentions = Vec::new();
+        let (gender, gender_confidence) = resolve_gender(&s, policy, &mut abstentions);
+        let (age_group, age_group_confidence) = match age_scheme {
+            Some(scheme) => resolve_age_bucket(scheme, policy, &mut abstentions),
+            None => resolve_age_group(&s, policy, &mut abstentions),
+        };
+        let (ethnicity, ethnicity_confidence) = resolve_ethnicity(&s, policy, &mut abstentions);
+        let (region, region_confidence) = resolve_region(&s, policy);
 
         Self {
             gender,
             gender_confidence,
-            ethnicity: s.ethnicity,
-            ethnicity_confidence: Confidence::from_probability(s.ethnicity_confidence),
+            ethnicity,
+            ethnicity_confidence,
             age_group,
             age_group_confidence,
+            age_scheme: age_scheme.map(|a| a.scheme.clone()),
+            age_estimate: s.age_estimate,
+            region,
+            region_confidence,
+            abstentions,
             organization: s.organization,
-            reasoning: s.reasoning,
+            self_reported: s.self_reported,
+            reasoning: render(&s.reasoning),
+            explanations: s.reasoning,
+            edge_case: EdgeCase::default(),
+            restricted: false,
             metrics: None,
         }
     }
-}
 
-impl FuzzyResponse {
+    pub fn with_restricted(mut self, restricted: bool) -> Self {
+        self.restricted = restricted;
+        self
+    }
+
+    pub fn with_edge_case(mut self, edge_case: EdgeCase) -> Self {
+        self.edge_case = edge_case;
+        self
+    }
+
     pub fn with_metrics_if<F>(mut self, condition: bool, f: F) -> Self
     where
         F: FnOnce() -> InferenceMetrics,
@@ -143,42 +243,94 @@ impl FuzzyResponse {
     }
 }
 
-fn resolve_gender(s: &InferenceSignal) -> (Gender, Confidence) {
-    let (gender, prob) = [
-        (Gender::Male, s.gender_male),
-        (Gender::Female, s.gender_female),
-        (Gender::Undetermined, s.gender_other),
-    ]
-    .into_iter()
-    .max_by(|a, b| a.1.total_cmp(&b.1))
-    .unwrap();
-
-    if prob == 0.0 {
+fn resolve_gender(
+    s: &InferenceSignal,
+    policy: &ConfidencePolicy,
+    abstentions: &mut Vec<Abstention>,
+) -> (Gender, Confidence) {
+    let Some(dist) = &s.gender else {
         return (Gender::Undetermined, Confidence::None);
+    };
+
+    match policy.assess(Attribute::Gender, dist.as_slice()) {
+        Ok(confidence) => {
+            let gender = match dist.argmax() {
+                (0, _) => Gender::Male,
+                (1, _) => Gender::Female,
+                _ if s.self_reported.contains(&Attribute::Gender) => Gender::Other,
+                _ => Gender::Undetermined,
+            };
+            (gender, confidence)
+        }
+        Err(abstention) => {
+            abstentions.push(abstention);
+            (Gender::Undetermined, Confidence::None)
+        }
     }
+}
+
+fn resolve_age_group(
+    s: &InferenceSignal,
+    policy: &ConfidencePolicy,
+    abstentions: &mut Vec<Abstention>,
+) -> (Option<String>, Confidence) {
+    let Some(dist) = &s.age else {
+        return (None, Confidence::None);
+    };
 
-    (gender, Confidence::from_probability(prob))
+    match policy.assess(Attribute::Age, dist.as_slice()) {
+        Ok(confidence) => (Some(dist.most_likely().0.label().to_string()), confidence),
+        Err(abstention) => {
+            abstentions.push(abstention);
+            (None, Confidence::None)
+        }
+    }
+}
+
+fn resolve_age_bucket(
+    scheme: &SchemedAge,
+    policy: &ConfidencePolicy,
+    abstentions: &mut Vec<Abstention>,
+) -> (Option<String>, Confidence) {
+    let probs: Vec<f64> = scheme.buckets.iter().map(|b| b.probability).collect();
+    let Some(top) = scheme
+        .buckets
+        .iter()
+        .max_by(|a, b| a.probability.total_cmp(&b.probability))
+    else {
+        return (None, Confidence::None);
+    };
+
+    match policy.assess(Attribute::Age, &probs) {
+        Ok(confidence) => (Some(top.label.clone()), confidence),
+        Err(abstention) => {
+            abstentions.push(abstention);
+            (None, Confidence::None)
+        }
+    }
 }
 
-fn resolve_age_group(s: &InferenceSignal) -> (Option<AgeGroup>, Confidence) {
-    let probs = [
-        (AgeGroup::Under18, s.age_group_under_18),
-        (AgeGroup::Age18_24, s.age_group_18_24),
-        (AgeGroup::Age25_34, s.age_group_25_34),
-        (AgeGroup::Age35_44, s.age_group_35_44),
-        (AgeGroup::Age45_54, s.age_group_45_54),
-        (AgeGroup::Age55_64, s.age_group_55_64),
-        (AgeGroup::Age65Plus, s.age_group_65_plus),
-    ];
-
-    let (group, prob) = probs
-        .into_iter()
-        .max_by(|a, b| a.1.total_cmp(&b.1))
-        .unwrap();
-
-    if prob == 0.0 {
+fn resolve_ethnicity(
+    s: &InferenceSignal,
+    policy: &ConfidencePolicy,
+    abstentions: &mut Vec<Abstention>,
+) -> (Option<Ethnicity>, Confidence) {
+    let Some(dist) = &s.ethnicity else {
         return (None, Confidence::None);
+    };
+
+    match policy.assess(Attribute::Ethnicity, dist.as_slice()) {
+        Ok(confidence) => (Some(dist.most_likely().0), confidence),
+        Err(abstention) => {
+            abstentions.push(abstention);
+            (None, Confidence::None)
+        }
     }
+}
 
-    (Some(group), Confidence::from_probability(prob))
+fn resolve_region(s: &InferenceSignal, policy: &ConfidencePolicy) -> (Option<String>, Confidence) {
+    match s.top_region() {
+        Some((region, prob)) => (Some(region.to_string()), policy.bands.classify(prob)),
+        None => (None, Confidence::None),
+    }
 }
@@ -1,63 +1,307 @@
+use chrono::NaiveDate;
 use futures::join;
 use ntex::web::{self, HttpResponse};
+use std::{sync::Arc, time::Instant};
 
 use crate::{
-    agent::{Agent, DomainAgent, LocalAgent, OnomasticAgent, VisionAgent},
-    api::{AnalyzeRequest, Format, FuzzyResponse, InferResponse, QueryParams, metrics},
-    core::{InferenceInput, fuse},
+    agent::{
+        Agent, DomainAgent, LocalAgent, OnomasticAgent, SelfReportAgent, VisionAgent, llm::Retrying,
+    },
+    api::{AnalyzeRequest, AppState, Format, FuzzyResponse, InferResponse, QueryParams, metrics},
+    core::{
+        Clock, FixedClock, InferenceInput, InferenceSignal, SchemedAge, SelfIdentified,
+        SignalSource, SystemClock, agreement, fuse,
+    },
+    edge,
 };
 
 pub async fn infer(
     body: web::types::Json<AnalyzeRequest>,
     query: web::types::Query<QueryParams>,
-    api_key: web::types::State<String>,
+    state: web::types::State<AppState>,
 ) -> HttpResponse {
     let input = InferenceInput {
         email: body.email.clone(),
         name: body.name.clone(),
         profile_pic_url: body.profile_pic_url.clone(),
         browsing_history: body.browsing_history.clone(),
+        declared: SelfIdentified {
+            pronouns: body.pronouns.clone(),
+            gender_identity: body.gender_identity.clone(),
+            birth_year: body.birth_year,
+        },
     };
 
-    let local = LocalAgent::new();
-    let onomast = OnomasticAgent::new(api_key.get_ref().clone());
-    let domain = DomainAgent::new(api_key.get_ref().clone());
+    let config = &state.config;
+    let age_scheme = match &query.age_scheme {
+        Some(name) => match config.age_scheme_for(query.tenant.as_deref(), name) {
+            Some(scheme) => Some((name, scheme)),
+            None => {
+                return HttpResponse::BadRequest()
+                    .json(&serde_json::json!({ "error": format!("unknown age scheme {name:?}") }));
+            }
+        },
+        None => None,
+    };
+
+    let mut edge_case = edge::inspect_input(&input);
+
+    let clock: Arc<dyn Clock> = match query.as_of {
+        Some(date) => Arc::new(FixedClock(date)),
+        None => Arc::new(SystemClock),
+    };
+    let today = clock.today();
+
+    let onomast_llm = Arc::new(Retrying::new(
+        state.llm.onomastic.clone(),
+        config.retry.onomastic.clone(),
+    ));
+    let vision_llm = Arc::new(Retrying::new(
+        state.llm.vision.clone(),
+        config.retry.vision.clone(),
+    ));
+    let domain_llm = Arc::new(Retrying::new(
+        state.llm.domain.clone(),
+        config.retry.domain.clone(),
+    ));
 
-    let signals = if input.profile_pic_url.is_some() {
-        let vision = VisionAgent::new(api_key.get_ref().clone());
+    let local = LocalAgent::new(state.config.birth_year.clone(), clock.clone());
+    let onomast = OnomasticAgent::new(onomast_llm.clone(), config.sampling.onomastic.clone())
+        .with_scoring(config.onomastic_scoring);
+    let domain = DomainAgent::new(domain_llm.clone());
+
+    let mut inferred = if input.profile_pic_url.is_some() {
+        let vision = VisionAgent::new(vision_llm.clone(), config.sampling.vision.clone());
 
         let (local_signal, onomast_signal, vision_signal, domain_signal) = join!(
-            local.analyze(&input),
-            onomast.analyze(&input),
-            vision.analyze(&input),
-            domain.analyze(&input)
+            unless_declared(&local, &input, today),
+            unless_declared(&onomast, &input, today),
+            unless_declared(&vision, &input, today),
+            unless_declared(&domain, &input, today)
         );
 
         vec![local_signal, onomast_signal, vision_signal, domain_signal]
     } else {
         let (local_signal, onomast_signal, domain_signal) = join!(
-            local.analyze(&input),
-            onomast.analyze(&input),
-            domain.analyze(&input)
+            unless_declared(&local, &input, today),
+            unless_declared(&onomast, &input, today),
+            unless_declared(&domain, &input, today)
         );
         vec![local_signal, onomast_signal, domain_signal]
     };
 
-    let fused = fuse(signals.clone());
+    for signal in inferred.iter_mut().flatten() {
+        signal.attempts = match signal.source {
+            SignalSource::Onomastic => Some(onomast_llm.attempts()),
+            SignalSource::Vision => Some(vision_llm.attempts()),
+            SignalSource::Domain => Some(domain_llm.attempts()),
+            _ => None,
+        };
+    }
+
+    let declared = if input.declared.is_empty() {
+        None
+    } else {
+        Some(run(&SelfReportAgent::new(clock), &input).await)
+    };
+    let signals: Vec<_> = declared
+        .into_iter()
+        .chain(inferred.into_iter().flatten())
+        .collect();
+
+    let mut signals = state.calibration.apply(signals, config.onomastic_scoring);
+
+    let weights = &config.fusion_weights;
+    let strategy = query
+        .strategy
+        .unwrap_or_else(|| config.strategy_for(query.tenant.as_deref()))
+        .build(&config.bayesian_prior);
+    let mut fused = fuse(signals.clone(), weights, strategy.as_ref());
+    let mut agreement = agreement::assess(&signals, &fused, config.disagreement_threshold);
+    state.rules.apply(&input, &signals, &mut fused);
+
+    let minor_protection = config.minor_protection_for(query.tenant.as_deref());
+    let restriction = minor_protection.apply(&mut fused);
+    let restricted = restriction.is_some();
+    if restricted {
+        // Source signals feed metrics, disagreements and edge reasons, so they
+        // must not keep what the fused response withholds
+        signals.iter_mut().for_each(|s| minor_protection.scrub(s));
+        agreement = agreement::assess(&signals, &fused, config.disagreement_threshold);
+    }
+    edge::inspect_signals(&mut edge_case, &signals, &agreement);
+
+    let age_scheme = age_scheme.map(|(name, scheme)| SchemedAge {
+        scheme: name.clone(),
+        buckets: scheme.bin(&fused, today),
+    });
 
     let include_metrics = !query.minimal.unwrap_or(false);
+    let build_metrics = || {
+        metrics::build_metrics(
+            &signals,
+            &input,
+            &agreement,
+            &edge_case,
+            weights,
+            strategy.as_ref(),
+            state.calibration.version.as_deref(),
+        )
+        .with_restriction(restriction.clone())
+    };
 
     match query.format {
         Format::Raw => {
             let response = InferResponse::from(fused)
-                .with_metrics_if(include_metrics, || metrics::build_metrics(&signals, &input));
+                .with_age_scheme(age_scheme)
+                .with_restricted(restricted)
+                .with_edge_case(edge_case.clone())
+                .with_metrics_if(include_metrics, build_metrics);
             HttpResponse::Ok().json(&response)
         }
 
         Format::Fuzzy => {
-            let response = FuzzyResponse::from(fused)
-                .with_metrics_if(include_metrics, || metrics::build_metrics(&signals, &input));
+            let policy = config.confidence_for(query.tenant.as_deref());
+            let response = FuzzyResponse::new(fused, policy, age_scheme.as_ref())
+                .with_restricted(restricted)
+                .with_edge_case(edge_case.clone())
+                .with_metrics_if(include_metrics, build_metrics);
             HttpResponse::Ok().json(&response)
         }
     }
 }
+
+// Agents whose every attribute was self-reported have nothing left to infer
+async fn unless_declared(
+    agent: &impl Agent,
+    input: &InferenceInput,
+    today: NaiveDate,
+) -> Option<InferenceSignal> {
+    let declared = agent
+        .attributes()
+        .iter()
+        .all(|a| input.declared.declares(*a, today));
+    if declared {
+        return None;
+    }
+
+    Some(run(agent, input).await)
+}
+
+// Failures become empty signals that carry the error into metrics
+async fn run(agent: &impl Agent, input: &InferenceInput) -> InferenceSignal {
+    let start = Instant::now();
+    agent.analyze(input).await.unwrap_or_else(|error| {
+        let mut signal = InferenceSignal::failed(agent.source(), error);
+        signal.latency_ms = start.elapsed().as_millis() as u64;
+        signal
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::future::BoxFuture;
+    use ntex::web::{App, test};
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::{
+        agent::{
+            AgentError,
+            llm::{ChatRequest, ChatResponse, LlmProvider, LlmProviders, ResponseFormat},
+        },
+        api::configure,
+    };
+
+    // Answers every request with the canned JSON for its response schema
+    #[derive(Debug)]
+    struct Canned;
+
+    impl LlmProvider for Canned {
+        fn chat<'a>(
+            &'a self,
+            request: &'a ChatRequest,
+        ) -> BoxFuture<'a, Result<ChatResponse, AgentError>> {
+            let Some(ResponseFormat::JsonSchema { json_schema }) = &request.response_format else {
+                unreachable!("every agent asks for a schema");
+            };
+            let content = match json_schema.name.as_str() {
+                "onomastic_result" => json!({
+                    "gender_male": 0.1,
+                    "gender_female": 0.9,
+                    "ethnicity": { "european": 0.8, "african": 0.2 },
+                    "reasoning": "Jane is a common female given name of European origin."
+                }),
+                "vision_result" => json!({
+                    "gender_male": 0.9,
+                    "gender_female": 0.1,
+                    "age_group": "under_18",
+                    "age_confidence": 0.8,
+                    "is_human": true,
+                    "reasoning": "A boy with short hair."
+                }),
+                _ => json!({
+                    "name": null,
+                    "category": null,
+                    "employee_count": null,
+                    "employee_count_source": null,
+                    "hq_country": null
+                }),
+            };
+            let choice = json!({ "message": { "content": content.to_string() } });
+            let choices = vec![choice; request.n.unwrap_or(1) as usize];
+            let response = serde_json::from_value(json!({ "choices": choices })).unwrap();
+            Box::pin(async move { Ok(response) })
+        }
+    }
+
+    fn state() -> AppState {
+        let llm: Arc<dyn LlmProvider> = Arc::new(Canned);
+        AppState {
+            llm: LlmProviders {
+                onomastic: llm.clone(),
+                vision: llm.clone(),
+                domain: llm,
+            },
+            config: Default::default(),
+            calibration: Default::default(),
+            rules: Default::default(),
+        }
+    }
+
+    #[ntex::test]
+    async fn restricted_response_leaks_no_suppressed_attribute() {
+        let app = test::init_service(App::new().state(state()).configure(configure)).await;
+        let request = test::TestRequest::post()
+            .uri("/v1/infer?as_of=2026-06-01")
+            .set_json(&json!({
+                "email": "jane2012@school.edu",
+                "name": "Jane",
+                "pronouns": "she/her",
+                "profile_pic_url": "https://example.com/jane.png"
+            }))
+            .to_request();
+        let mut body: Value = test::read_response_json(&app, request).await;
+
+        assert_eq!(body["restricted"], true);
+        assert_eq!(body["gender"], "undetermined");
+        assert!(body["ethnicity"].is_null());
+        for key in [
+            "gender",
+            "gender_confidence",
+            "ethnicity",
+            "ethnicity_confidence",
+        ] {
+            body.as_object_mut().unwrap().remove(key);
+        }
+        // Configuration and the restriction itself name attributes, not values
+        let metrics = body["metrics"].as_object_mut().unwrap();
+        metrics.remove("fusion_weights");
+        metrics.remove("restriction");
+
+        let text = body.to_string().to_lowercase();
+        for word in ["gender", "ethnicity", "female", "european", "she/her"] {
+            assert!(!text.contains(word), "{word} leaked: {text}");
+        }
+    }
+}
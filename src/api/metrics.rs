@@ -1,9 +1,21 @@
 use chrono::Utc;
 use uuid::Uuid;
 
-use crate::core::{InferenceInput, InferenceMetrics, InferenceSignal, SourceMetrics};
+use crate::core::{
+    Agreement, FusionStrategy, FusionWeights, InferenceInput, InferenceMetrics, InferenceSignal,
+    SourceMetrics, SourceStatus, agreement::source_confidence,
+};
+use crate::edge::EdgeCase;
 
-pub fn build_metrics(signals: &[InferenceSignal], input: &InferenceInput) -> InferenceMetrics {
+pub fn build_metrics(
+    signals: &[InferenceSignal],
+    input: &InferenceInput,
+    agreement: &Agreement,
+    edge_case: &EdgeCase,
+    weights: &FusionWeights,
+    strategy: &dyn FusionStrategy,
+    calibration_version: Option<&str>,
+) -> InferenceMetrics {
     let mut inputs_provided = vec!["email"];
     if input.name.is_some() {
         inputs_provided.push("name");
@@ -14,6 +26,15 @@ pub fn build_metrics(signals: &[InferenceSignal], input: &InferenceInput) -> Inf
     if input.browsing_history.is_some() {
         inputs_provided.push("browsing_history");
     }
+    if input.declared.pronouns.is_some() {
+        inputs_provided.push("pronouns");
+    }
+    if input.declared.gender_identity.is_some() {
+        inputs_provided.push("gender_identity");
+    }
+    if input.declared.birth_year.is_some() {
+        inputs_provided.push("birth_year");
+    }
 
     let sources_used: Vec<SourceMetrics> = signals
         .iter()
@@ -25,22 +46,36 @@ pub fn build_metrics(signals: &[InferenceSignal], input: &InferenceInput) -> Inf
             if s.birth_year.is_some() {
                 contributed.push("birth_year".to_string());
             }
-            if s.has_gender_signal() {
+            if s.gender.is_some() {
                 contributed.push("gender".to_string());
             }
-            if s.has_age_signal() {
+            if s.age.is_some() {
                 contributed.push("age".to_string());
             }
             if s.ethnicity.is_some() {
                 contributed.push("ethnicity".to_string());
             }
+            if s.has_region_signal() {
+                contributed.push("region".to_string());
+            }
+
+            let status = match &s.failure {
+                Some(error) => SourceStatus::Failed {
+                    error: error.clone(),
+                },
+                None if contributed.is_empty() => SourceStatus::NoEvidence,
+                None => SourceStatus::Ok,
+            };
 
             SourceMetrics {
                 source: s.source.clone(),
+                status,
                 latency_ms: s.latency_ms,
                 tokens_used: s.tokens_used,
+                attempts: s.attempts,
                 contributed,
-                confidence: 1.0,
+                confidence: source_confidence(s),
+                sampling: s.sampling.clone(),
             }
         })
         .collect();
@@ -50,9 +85,14 @@ pub fn build_metrics(signals: &[InferenceSignal], input: &InferenceInput) -> Inf
         timestamp: Utc::now().to_rfc3339(),
         inputs_provided: inputs_provided.into_iter().map(String::from).collect(),
         sources_used,
-        sources_agreed: true,
-        fusion_confidence: 1.0,
-        edge_case: false,
+        sources_agreed: agreement.sources_agreed(),
+        fusion_confidence: agreement.fusion_confidence,
+        disagreements: agreement.disagreements.clone(),
+        fusion_strategy: strategy.kind(),
+        fusion_weights: weights.clone(),
+        calibration_version: calibration_version.map(String::from),
+        edge_case: edge_case.flagged,
+        restriction: None,
         total_tokens: signals.iter().filter_map(|s| s.tokens_used).sum(),
         estimated_cost_usd: 0.0,
         total_latency_ms: signals.iter().map(|s| s.latency_ms).max().unwrap_or(0),
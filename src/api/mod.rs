@@ -2,6 +2,8 @@ pub mod dto;
 pub mod handler;
 pub mod metrics;
 pub mod router;
+pub mod state;
 
 pub use dto::*;
 pub use router::configure;
+pub use state::AppState;
@@ -0,0 +1,11 @@
+use crate::{
+    agent::llm::LlmProviders, calibration::CalibrationSet, config::Config, rules::RuleSet,
+};
+
+#[derive(Debug, Clone)]
+pub struct AppState {
+    pub llm: LlmProviders,
+    pub config: Config,
+    pub calibration: CalibrationSet,
+    pub rules: RuleSet,
+}
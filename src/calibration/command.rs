@@ -0,0 +1,112 @@
+use std::io::{Error, ErrorKind};
+
+use super::{
+    evaluate::evaluate,
+    fit::{LabeledOutcome, Method, fit},
+};
+use crate::agent::Scoring;
+
+const USAGE: &str = "usage: aura calibrate <outcomes.jsonl> <output.json> [--method isotonic|platt] [--version <v>] [--scoring verbalized|logprobs]";
+const EVALUATE_USAGE: &str = "usage: aura evaluate <outcomes.jsonl>";
+
+// `aura calibrate`: fits calibration maps from JSON-lines labeled outcomes
+pub fn run(args: Vec<String>) -> std::io::Result<()> {
+    let invalid = |msg: String| Error::new(ErrorKind::InvalidInput, format!("{msg}\n{USAGE}"));
+
+    let mut positional = Vec::new();
+    let mut method = Method::Isotonic;
+    let mut version = chrono::Utc::now().format("%Y%m%d%H%M%S").to_string();
+    let mut scoring = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--method" => {
+                let value = args.next().unwrap_or_default();
+                method = serde_json::from_value(serde_json::Value::String(value.clone()))
+                    .map_err(|_| invalid(format!("Unknown method '{value}'")))?;
+            }
+            "--version" => {
+                version = args
+                    .next()
+                    .ok_or_else(|| invalid("--version needs a value".to_string()))?;
+            }
+            "--scoring" => {
+                let value = args.next().unwrap_or_default();
+                scoring =
+                    serde_json::from_value::<Scoring>(serde_json::Value::String(value.clone()))
+                        .map(Some)
+                        .map_err(|_| invalid(format!("Unknown scoring '{value}'")))?;
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let [input, output] = positional.as_slice() else {
+        return Err(invalid("Expected an input and an output path".to_string()));
+    };
+
+    // Maps fitted on one scoring mode do not transfer to the other
+    let outcomes: Vec<LabeledOutcome> = read_outcomes(input)
+        .map_err(invalid)?
+        .into_iter()
+        .filter(|o| scoring.is_none_or(|s| s == o.scoring))
+        .collect();
+
+    let set = fit(&outcomes, method, version);
+    std::fs::write(output, serde_json::to_string_pretty(&set)?)?;
+
+    println!(
+        "Fitted {} calibration maps from {} outcomes into {output} (version {}).",
+        set.maps.len(),
+        outcomes.len(),
+        set.version.as_deref().unwrap_or("-")
+    );
+    Ok(())
+}
+
+// `aura evaluate`: scores labeled outcomes per source, attribute and scoring mode
+pub fn evaluate_command(args: Vec<String>) -> std::io::Result<()> {
+    let invalid =
+        |msg: String| Error::new(ErrorKind::InvalidInput, format!("{msg}\n{EVALUATE_USAGE}"));
+
+    let [input] = args.as_slice() else {
+        return Err(invalid("Expected an input path".to_string()));
+    };
+    let outcomes = read_outcomes(input).map_err(invalid)?;
+
+    println!(
+        "{:<12} {:<12} {:<11} {:>6} {:>7} {:>8} {:>6}",
+        "source", "attribute", "scoring", "n", "brier", "log_loss", "ece"
+    );
+    for e in evaluate(&outcomes) {
+        println!(
+            "{:<12} {:<12} {:<11} {:>6} {:>7.4} {:>8.4} {:>6.4}",
+            label(&e.source),
+            label(&e.attribute),
+            label(&e.scoring),
+            e.count,
+            e.brier,
+            e.log_loss,
+            e.ece
+        );
+    }
+    Ok(())
+}
+
+fn read_outcomes(input: &str) -> Result<Vec<LabeledOutcome>, String> {
+    std::fs::read_to_string(input)
+        .map_err(|e| format!("Cannot read {input}: {e}"))?
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()
+        .map_err(|e| format!("Invalid outcome in {input}: {e}"))
+}
+
+fn label(value: &impl serde::Serialize) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(s)) => s,
+        _ => "-".to_string(),
+    }
+}
@@ -0,0 +1,111 @@
+use super::fit::LabeledOutcome;
+use crate::{
+    agent::Scoring,
+    core::{Attribute, SignalSource},
+};
+
+const BINS: usize = 10;
+const EPSILON: f64 = 1e-6;
+
+// (predicted, outcome as 0 or 1)
+type Samples = Vec<(f64, f64)>;
+
+// Proper scoring rules for one source, attribute and scoring mode
+#[derive(Debug, Clone, PartialEq)]
+pub struct Evaluation {
+    pub source: SignalSource,
+    pub attribute: Attribute,
+    pub scoring: Scoring,
+    pub count: usize,
+    pub brier: f64,
+    pub log_loss: f64,
+    // Expected calibration error over equal-width bins
+    pub ece: f64,
+}
+
+pub fn evaluate(outcomes: &[LabeledOutcome]) -> Vec<Evaluation> {
+    let mut groups: Vec<(&SignalSource, Attribute, Scoring, Samples)> = Vec::new();
+
+    for o in outcomes {
+        let sample = (
+            o.predicted.clamp(0.0, 1.0),
+            if o.outcome { 1.0 } else { 0.0 },
+        );
+        match groups
+            .iter_mut()
+            .find(|(s, a, m, _)| **s == o.source && *a == o.attribute && *m == o.scoring)
+        {
+            Some((_, _, _, samples)) => samples.push(sample),
+            None => groups.push((&o.source, o.attribute, o.scoring, vec![sample])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(source, attribute, scoring, samples)| {
+            let n = samples.len() as f64;
+            let brier = samples.iter().map(|(p, y)| (p - y).powi(2)).sum::<f64>() / n;
+            let log_loss = -samples
+                .iter()
+                .map(|(p, y)| {
+                    let p = p.clamp(EPSILON, 1.0 - EPSILON);
+                    y * p.ln() + (1.0 - y) * (1.0 - p).ln()
+                })
+                .sum::<f64>()
+                / n;
+
+            // (sum of p, sum of y, count) per bin
+            let mut bins = [(0.0, 0.0, 0.0); BINS];
+            for (p, y) in &samples {
+                let bin = &mut bins[((p * BINS as f64) as usize).min(BINS - 1)];
+                *bin = (bin.0 + p, bin.1 + y, bin.2 + 1.0);
+            }
+            let ece = bins.iter().map(|(p, y, _)| (p - y).abs()).sum::<f64>() / n;
+
+            Evaluation {
+                source: source.clone(),
+                attribute,
+                scoring,
+                count: samples.len(),
+                brier,
+                log_loss,
+                ece,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(scoring: Scoring, predicted: f64, outcome: bool) -> LabeledOutcome {
+        LabeledOutcome {
+            source: SignalSource::Onomastic,
+            attribute: Attribute::Gender,
+            scoring,
+            predicted,
+            outcome,
+        }
+    }
+
+    #[test]
+    fn scoring_modes_are_evaluated_apart() {
+        let outcomes = [
+            outcome(Scoring::Verbalized, 0.9, false),
+            outcome(Scoring::Verbalized, 0.9, true),
+            outcome(Scoring::Logprobs, 0.5, false),
+            outcome(Scoring::Logprobs, 0.5, true),
+        ];
+
+        let evaluations = evaluate(&outcomes);
+        assert_eq!(evaluations.len(), 2);
+
+        let [verbalized, logprobs] = evaluations.as_slice() else {
+            unreachable!()
+        };
+        assert_eq!(verbalized.scoring, Scoring::Verbalized);
+        assert!(logprobs.brier < verbalized.brier);
+        assert!(logprobs.ece < 1e-9);
+    }
+}
@@ -0,0 +1,125 @@
+use serde::Deserialize;
+
+use super::{CalibrationEntry, CalibrationMap, CalibrationSet, map};
+use crate::{
+    agent::Scoring,
+    core::{Attribute, SignalSource},
+};
+
+const PLATT_ITERATIONS: usize = 50;
+
+type Samples = Vec<(f64, bool)>;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LabeledOutcome {
+    pub source: SignalSource,
+    pub attribute: Attribute,
+    #[serde(default)]
+    pub scoring: Scoring,
+    pub predicted: f64,
+    pub outcome: bool,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Method {
+    Isotonic,
+    Platt,
+}
+
+pub fn fit(outcomes: &[LabeledOutcome], method: Method, version: String) -> CalibrationSet {
+    let mut groups: Vec<(SignalSource, Attribute, Scoring, Samples)> = Vec::new();
+
+    for o in outcomes {
+        let sample = (o.predicted.clamp(0.0, 1.0), o.outcome);
+        match groups
+            .iter_mut()
+            .find(|(s, a, m, _)| *s == o.source && *a == o.attribute && *m == o.scoring)
+        {
+            Some((_, _, _, samples)) => samples.push(sample),
+            None => groups.push((o.source.clone(), o.attribute, o.scoring, vec![sample])),
+        }
+    }
+
+    let maps = groups
+        .into_iter()
+        .map(|(source, attribute, scoring, samples)| CalibrationEntry {
+            source,
+            attribute,
+            scoring,
+            map: match method {
+                Method::Isotonic => fit_isotonic(samples),
+                Method::Platt => fit_platt(&samples),
+            },
+        })
+        .collect();
+
+    CalibrationSet {
+        version: Some(version),
+        maps,
+    }
+}
+
+// Pool-adjacent-violators
+fn fit_isotonic(mut samples: Samples) -> CalibrationMap {
+    samples.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    // (sum of x, sum of y, count)
+    let mut blocks: Vec<(f64, f64, f64)> = Vec::new();
+    for (x, y) in samples {
+        blocks.push((x, if y { 1.0 } else { 0.0 }, 1.0));
+
+        while blocks.len() > 1 {
+            let (_, y1, n1) = blocks[blocks.len() - 1];
+            let (_, y0, n0) = blocks[blocks.len() - 2];
+            if y0 / n0 <= y1 / n1 {
+                break;
+            }
+            let (x1, y1, n1) = blocks.pop().unwrap();
+            let last = blocks.last_mut().unwrap();
+            *last = (last.0 + x1, last.1 + y1, last.2 + n1);
+        }
+    }
+
+    CalibrationMap::Isotonic {
+        points: blocks.into_iter().map(|(x, y, n)| (x / n, y / n)).collect(),
+    }
+}
+
+// Logistic regression of the outcome on logit(predicted), fitted by Newton's method
+fn fit_platt(samples: &[(f64, bool)]) -> CalibrationMap {
+    let (mut a, mut b) = (1.0, 0.0);
+
+    for _ in 0..PLATT_ITERATIONS {
+        let (mut ga, mut gb, mut haa, mut hab, mut hbb) = (0.0, 0.0, 0.0, 0.0, 0.0);
+
+        for &(p, y) in samples {
+            let s = map::logit(p);
+            let q = map::sigmoid(a * s + b);
+            let err = q - if y { 1.0 } else { 0.0 };
+            let w = (q * (1.0 - q)).max(1e-9);
+
+            ga += err * s;
+            gb += err;
+            haa += w * s * s;
+            hab += w * s;
+            hbb += w;
+        }
+
+        let det = haa * hbb - hab * hab;
+        if det.abs() < 1e-12 {
+            break;
+        }
+
+        let da = (hbb * ga - hab * gb) / det;
+        let db = (haa * gb - hab * ga) / det;
+        a -= da;
+        b -= db;
+
+        if da.abs() < 1e-9 && db.abs() < 1e-9 {
+            break;
+        }
+    }
+
+    CalibrationMap::Platt { a, b }
+}
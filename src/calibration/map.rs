@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+const EPSILON: f64 = 1e-6;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "method", rename_all = "lowercase")]
+pub enum CalibrationMap {
+    // p' = sigmoid(a * logit(p) + b)
+    Platt { a: f64, b: f64 },
+    // Monotone piecewise-linear map through (raw, calibrated) points
+    Isotonic { points: Vec<(f64, f64)> },
+}
+
+impl CalibrationMap {
+    pub fn apply(&self, p: f64) -> f64 {
+        match self {
+            Self::Platt { a, b } => sigmoid(a * logit(p) + b),
+            Self::Isotonic { points } => interpolate(points, p),
+        }
+    }
+}
+
+pub fn logit(p: f64) -> f64 {
+    let p = p.clamp(EPSILON, 1.0 - EPSILON);
+    (p / (1.0 - p)).ln()
+}
+
+pub fn sigmoid(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+fn interpolate(points: &[(f64, f64)], p: f64) -> f64 {
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return p;
+    };
+
+    if p <= first.0 {
+        return first.1;
+    }
+    if p >= last.0 {
+        return last.1;
+    }
+
+    points
+        .windows(2)
+        .find(|w| p >= w[0].0 && p <= w[1].0)
+        .map(|w| {
+            let (x0, y0) = w[0];
+            let (x1, y1) = w[1];
+            if x1 - x0 < EPSILON {
+                y1
+            } else {
+                y0 + (y1 - y0) * (p - x0) / (x1 - x0)
+            }
+        })
+        .unwrap_or(p)
+}
@@ -0,0 +1,181 @@
+pub mod command;
+pub mod evaluate;
+pub mod fit;
+pub mod map;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    agent::Scoring,
+    core::{Attribute, InferenceSignal, SignalSource, distribution::Distribution},
+};
+
+pub use map::CalibrationMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationEntry {
+    pub source: SignalSource,
+    pub attribute: Attribute,
+    // Only the onomastic agent has more than one mode; every other source is verbalized
+    #[serde(default)]
+    pub scoring: Scoring,
+    pub map: CalibrationMap,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CalibrationSet {
+    pub version: Option<String>,
+    pub maps: Vec<CalibrationEntry>,
+}
+
+impl CalibrationSet {
+    pub fn load(path: &str) -> Self {
+        let raw = std::fs::read_to_string(path)
+            .unwrap_or_else(|e| panic!("Cannot read calibration file {path}: {e}"));
+
+        serde_json::from_str(&raw)
+            .unwrap_or_else(|e| panic!("Invalid calibration file {path}: {e}"))
+    }
+
+    fn map_for(
+        &self,
+        source: &SignalSource,
+        attribute: Attribute,
+        scoring: Scoring,
+    ) -> Option<&CalibrationMap> {
+        self.maps
+            .iter()
+            .find(|e| e.source == *source && e.attribute == attribute && e.scoring == scoring)
+            .map(|e| &e.map)
+    }
+
+    // Maps fitted on one scoring mode do not transfer to the other
+    pub fn apply(
+        &self,
+        signals: Vec<InferenceSignal>,
+        onomastic_scoring: Scoring,
+    ) -> Vec<InferenceSignal> {
+        signals
+            .into_iter()
+            .map(|s| {
+                let scoring = match s.source {
+                    SignalSource::Onomastic => onomastic_scoring,
+                    _ => Scoring::default(),
+                };
+                self.calibrate(s, scoring)
+            })
+            .collect()
+    }
+
+    fn calibrate(&self, mut signal: InferenceSignal, scoring: Scoring) -> InferenceSignal {
+        if let Some(gender) = signal.gender
+            && let Some(map) = self.map_for(&signal.source, Attribute::Gender, scoring)
+        {
+            signal.gender = Some(calibrate_distribution(map, gender));
+        }
+
+        if let Some(age) = signal.age
+            && let Some(map) = self.map_for(&signal.source, Attribute::Age, scoring)
+        {
+            signal.age = Some(calibrate_distribution(map, age));
+        }
+
+        if let Some(ethnicity) = signal.ethnicity
+            && let Some(map) = self.map_for(&signal.source, Attribute::Ethnicity, scoring)
+        {
+            signal.ethnicity = Some(calibrate_distribution(map, ethnicity));
+        }
+
+        if signal.has_region_signal()
+            && let Some(map) = self.map_for(&signal.source, Attribute::Region, scoring)
+        {
+            for p in signal.region.values_mut() {
+                *p = map.apply(*p);
+            }
+        }
+
+        signal
+    }
+}
+
+// Calibrates each outcome one-vs-rest, then renormalizes
+fn calibrate_distribution<const N: usize, K>(
+    map: &CalibrationMap,
+    dist: Distribution<N, K>,
+) -> Distribution<N, K> {
+    dist.map(|p| map.apply(p)).unwrap_or(dist)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fit::{LabeledOutcome, Method, fit};
+    use super::*;
+    use crate::core::GenderDistribution;
+
+    fn outcomes() -> Vec<LabeledOutcome> {
+        overconfident(SignalSource::Vision, Scoring::Verbalized)
+    }
+
+    fn overconfident(source: SignalSource, scoring: Scoring) -> Vec<LabeledOutcome> {
+        // The source says 0.9 but is right only 60% of the time
+        (0..10)
+            .map(|i| LabeledOutcome {
+                source: source.clone(),
+                attribute: Attribute::Gender,
+                scoring,
+                predicted: 0.9,
+                outcome: i < 6,
+            })
+            .chain((0..10).map(|i| LabeledOutcome {
+                source: source.clone(),
+                attribute: Attribute::Gender,
+                scoring,
+                predicted: 0.1,
+                outcome: i < 2,
+            }))
+            .collect()
+    }
+
+    #[test]
+    fn isotonic_fit_shrinks_overconfidence() {
+        let set = fit(&outcomes(), Method::Isotonic, "test".to_string());
+        let map = set
+            .map_for(
+                &SignalSource::Vision,
+                Attribute::Gender,
+                Scoring::Verbalized,
+            )
+            .unwrap();
+        assert!((map.apply(0.9) - 0.6).abs() < 1e-9);
+        assert!((map.apply(0.1) - 0.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn platt_fit_shrinks_overconfidence() {
+        let set = fit(&outcomes(), Method::Platt, "test".to_string());
+        let map = set
+            .map_for(
+                &SignalSource::Vision,
+                Attribute::Gender,
+                Scoring::Verbalized,
+            )
+            .unwrap();
+        assert!((map.apply(0.9) - 0.6).abs() < 0.01);
+    }
+
+    #[test]
+    fn maps_apply_only_to_their_scoring_mode() {
+        let outcomes = overconfident(SignalSource::Onomastic, Scoring::Logprobs);
+        let set = fit(&outcomes, Method::Isotonic, "test".to_string());
+
+        let mut signal = InferenceSignal::new(SignalSource::Onomastic);
+        signal.gender = Some(GenderDistribution::from_binary(0.9, 0.1).unwrap());
+        let male = |scoring| {
+            let signals = set.apply(vec![signal.clone()], scoring);
+            signals[0].gender.as_ref().unwrap().male()
+        };
+
+        assert!((male(Scoring::Verbalized) - 0.9).abs() < 1e-9);
+        assert!(male(Scoring::Logprobs) < 0.7);
+    }
+}
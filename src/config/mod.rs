@@ -0,0 +1,101 @@
+pub mod tenant;
+
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::{
+    agent::{
+        BirthYearModel, SamplingConfig, Scoring,
+        llm::{AgentLlmConfig, LlmConfig, RetryConfig},
+    },
+    core::{AgeScheme, BayesianPrior, ConfidencePolicy, FusionWeights, MinorPolicy, StrategyKind},
+};
+
+pub use tenant::TenantConfig;
+
+const DEFAULT_PATH: &str = "aura.json";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub llm: LlmConfig,
+    pub agent_llm: AgentLlmConfig,
+    pub retry: RetryConfig,
+    pub fusion_weights: FusionWeights,
+    pub fusion_strategy: StrategyKind,
+    pub bayesian_prior: BayesianPrior,
+    pub disagreement_threshold: f64,
+    pub calibration_path: Option<String>,
+    pub rules_path: Option<String>,
+    pub birth_year: BirthYearModel,
+    pub sampling: SamplingConfig,
+    pub onomastic_scoring: Scoring,
+    pub confidence: ConfidencePolicy,
+    pub age_schemes: HashMap<String, AgeScheme>,
+    pub minor_protection: MinorPolicy,
+    pub tenants: HashMap<String, TenantConfig>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            llm: LlmConfig::default(),
+            agent_llm: AgentLlmConfig::default(),
+            retry: RetryConfig::default(),
+            fusion_weights: FusionWeights::default(),
+            fusion_strategy: StrategyKind::default(),
+            bayesian_prior: BayesianPrior::default(),
+            disagreement_threshold: 0.3,
+            calibration_path: None,
+            rules_path: None,
+            birth_year: BirthYearModel::default(),
+            sampling: SamplingConfig::default(),
+            onomastic_scoring: Scoring::default(),
+            confidence: ConfidencePolicy::default(),
+            age_schemes: HashMap::new(),
+            minor_protection: MinorPolicy::default(),
+            tenants: HashMap::new(),
+        }
+    }
+}
+
+impl Config {
+    pub fn load() -> Self {
+        let path = std::env::var("AURA_CONFIG").unwrap_or_else(|_| DEFAULT_PATH.to_string());
+
+        match std::fs::read_to_string(&path) {
+            Ok(raw) => serde_json::from_str(&raw)
+                .unwrap_or_else(|e| panic!("Invalid AURA config at {path}: {e}")),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn tenant(&self, tenant: Option<&str>) -> Option<&TenantConfig> {
+        self.tenants.get(tenant?)
+    }
+
+    pub fn strategy_for(&self, tenant: Option<&str>) -> StrategyKind {
+        self.tenant(tenant)
+            .and_then(|t| t.fusion_strategy)
+            .unwrap_or(self.fusion_strategy)
+    }
+
+    pub fn confidence_for(&self, tenant: Option<&str>) -> &ConfidencePolicy {
+        self.tenant(tenant)
+            .and_then(|t| t.confidence.as_ref())
+            .unwrap_or(&self.confidence)
+    }
+
+    pub fn minor_protection_for(&self, tenant: Option<&str>) -> &MinorPolicy {
+        self.tenant(tenant)
+            .and_then(|t| t.minor_protection.as_ref())
+            .unwrap_or(&self.minor_protection)
+    }
+
+    // Tenant schemes shadow global ones with the same name
+    pub fn age_scheme_for(&self, tenant: Option<&str>, name: &str) -> Option<&AgeScheme> {
+        self.tenant(tenant)
+            .and_then(|t| t.age_schemes.get(name))
+            .or_else(|| self.age_schemes.get(name))
+    }
+}
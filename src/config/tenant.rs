@@ -0,0 +1,13 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::core::{AgeScheme, ConfidencePolicy, MinorPolicy, StrategyKind};
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct TenantConfig {
+    pub fusion_strategy: Option<StrategyKind>,
+    pub confidence: Option<ConfidencePolicy>,
+    pub age_schemes: HashMap<String, AgeScheme>,
+    pub minor_protection: Option<MinorPolicy>,
+}
@@ -0,0 +1,151 @@
+use serde::Serialize;
+
+use super::{Attribute, InferenceSignal, SignalSource, distribution::Distribution};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Disagreement {
+    pub attribute: Attribute,
+    pub sources: [SignalSource; 2],
+    pub divergence: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Agreement {
+    pub fusion_confidence: f64,
+    pub disagreements: Vec<Disagreement>,
+}
+
+impl Agreement {
+    pub fn sources_agreed(&self) -> bool {
+        self.disagreements.is_empty()
+    }
+}
+
+pub fn source_confidence(signal: &InferenceSignal) -> f64 {
+    let mut scores = Vec::new();
+    if let Some(gender) = &signal.gender {
+        scores.push(1.0 - gender.normalized_entropy());
+    }
+    if let Some(age) = &signal.age {
+        scores.push(1.0 - age.normalized_entropy());
+    }
+    if let Some(ethnicity) = &signal.ethnicity {
+        scores.push(1.0 - ethnicity.normalized_entropy());
+    }
+
+    if scores.is_empty() {
+        // Organization and birth year are deterministic lookups
+        return if signal.organization.is_some() || signal.birth_year.is_some() {
+            1.0
+        } else {
+            0.0
+        };
+    }
+
+    // Samples that disagree with each other lower trust in their average
+    let spread = signal.sampling.as_ref().map_or(0.0, |s| s.spread);
+    (1.0 - spread) * scores.iter().sum::<f64>() / scores.len() as f64
+}
+
+pub fn assess(signals: &[InferenceSignal], fused: &InferenceSignal, threshold: f64) -> Agreement {
+    let mut disagreements = Vec::new();
+
+    let attribute_confidences = [
+        assess_attribute(
+            Attribute::Gender,
+            |s| s.gender.as_ref(),
+            signals,
+            fused,
+            threshold,
+            &mut disagreements,
+        ),
+        assess_attribute(
+            Attribute::Age,
+            |s| s.age.as_ref(),
+            signals,
+            fused,
+            threshold,
+            &mut disagreements,
+        ),
+        assess_attribute(
+            Attribute::Ethnicity,
+            |s| s.ethnicity.as_ref(),
+            signals,
+            fused,
+            threshold,
+            &mut disagreements,
+        ),
+    ];
+    let attribute_confidences: Vec<f64> = attribute_confidences.into_iter().flatten().collect();
+
+    let fusion_confidence = if attribute_confidences.is_empty() {
+        0.0
+    } else {
+        attribute_confidences.iter().sum::<f64>() / attribute_confidences.len() as f64
+    };
+
+    Agreement {
+        fusion_confidence,
+        disagreements,
+    }
+}
+
+// Confidence in the fused distribution, discounted by the worst pairwise JS
+// divergence between sources
+fn assess_attribute<const N: usize, K>(
+    attribute: Attribute,
+    select: fn(&InferenceSignal) -> Option<&Distribution<N, K>>,
+    signals: &[InferenceSignal],
+    fused: &InferenceSignal,
+    threshold: f64,
+    disagreements: &mut Vec<Disagreement>,
+) -> Option<f64> {
+    let fused = select(fused)?;
+    // A self-report overrides inference rather than disagreeing with it
+    let dists: Vec<_> = signals
+        .iter()
+        .filter(|s| s.source != SignalSource::SelfReported)
+        .filter_map(|s| Some((&s.source, select(s)?)))
+        .collect();
+
+    let mut max_divergence: f64 = 0.0;
+    for (i, (a, p)) in dists.iter().enumerate() {
+        for (b, q) in &dists[i + 1..] {
+            let divergence = p.js(q);
+            max_divergence = max_divergence.max(divergence);
+
+            if divergence > threshold {
+                disagreements.push(Disagreement {
+                    attribute,
+                    sources: [(*a).clone(), (*b).clone()],
+                    divergence,
+                });
+            }
+        }
+    }
+
+    Some((1.0 - fused.normalized_entropy()) * (1.0 - max_divergence))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::GenderDistribution;
+
+    #[test]
+    fn flags_opposing_sources() {
+        let mut onomast = InferenceSignal::new(SignalSource::Onomastic);
+        onomast.gender = GenderDistribution::new([0.05, 0.95, 0.0]).ok();
+
+        let mut vision = InferenceSignal::new(SignalSource::Vision);
+        vision.gender = GenderDistribution::new([0.9, 0.1, 0.0]).ok();
+
+        let mut fused = InferenceSignal::new(SignalSource::Local);
+        fused.gender = GenderDistribution::new([0.475, 0.525, 0.0]).ok();
+
+        let agreement = assess(&[onomast, vision], &fused, 0.3);
+        assert!(!agreement.sources_agreed());
+        assert_eq!(agreement.disagreements[0].attribute, Attribute::Gender);
+        assert!(agreement.fusion_confidence < 0.2);
+    }
+}
@@ -0,0 +1,22 @@
+use chrono::NaiveDate;
+
+pub trait Clock: Send + Sync {
+    fn today(&self) -> NaiveDate;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn today(&self) -> NaiveDate {
+        chrono::Utc::now().date_naive()
+    }
+}
+
+// Pins inference to a date, e.g. a historical signup for backfills
+pub struct FixedClock(pub NaiveDate);
+
+impl Clock for FixedClock {
+    fn today(&self) -> NaiveDate {
+        self.0
+    }
+}
@@ -0,0 +1,91 @@
+use chrono::{Datelike, NaiveDate};
+
+use super::{Attribute, GenderDistribution};
+
+// Oldest self-reported age accepted as genuine
+const MAX_AGE: i32 = 120;
+
+// Attributes the person stated about themselves, e.g. from an SSO or
+// directory profile
+#[derive(Debug, Clone, Default)]
+pub struct SelfIdentified {
+    pub pronouns: Option<String>,
+    pub gender_identity: Option<String>,
+    pub birth_year: Option<u16>,
+}
+
+impl SelfIdentified {
+    pub fn is_empty(&self) -> bool {
+        self.pronouns.is_none() && self.gender_identity.is_none() && self.birth_year.is_none()
+    }
+
+    // Gender identity wins over pronouns; anything else self-described is "other"
+    pub fn gender(&self) -> Option<GenderDistribution> {
+        let index = self
+            .gender_identity
+            .as_deref()
+            .and_then(identity_index)
+            .or_else(|| self.pronouns.as_deref().and_then(pronoun_index))?;
+        Some(GenderDistribution::one_hot(index))
+    }
+
+    pub fn birth_year(&self, today: NaiveDate) -> Option<u16> {
+        self.birth_year
+            .filter(|year| (0..=MAX_AGE).contains(&(today.year() - *year as i32)))
+    }
+
+    pub fn declares(&self, attribute: Attribute, today: NaiveDate) -> bool {
+        match attribute {
+            Attribute::Gender => self.gender().is_some(),
+            Attribute::Age => self.birth_year(today).is_some(),
+            Attribute::Ethnicity | Attribute::Region | Attribute::Organization => false,
+        }
+    }
+}
+
+fn identity_index(identity: &str) -> Option<usize> {
+    match identity.trim().to_lowercase().as_str() {
+        "male" | "man" | "m" => Some(0),
+        "female" | "woman" | "f" => Some(1),
+        "" | "unknown" | "prefer not to say" => None,
+        _ => Some(2),
+    }
+}
+
+// The first listed set counts, e.g. "she/they"
+fn pronoun_index(pronouns: &str) -> Option<usize> {
+    let pronouns = pronouns.trim().to_lowercase();
+    match pronouns.split(['/', ',', ' ']).next()? {
+        "he" | "him" => Some(0),
+        "she" | "her" => Some(1),
+        "they" | "them" | "xe" | "ze" | "ey" => Some(2),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identity_wins_over_pronouns() {
+        let declared = SelfIdentified {
+            pronouns: Some("she/they".to_string()),
+            ..SelfIdentified::default()
+        };
+        assert_eq!(declared.gender().unwrap().argmax().0, 1);
+
+        let declared = SelfIdentified {
+            pronouns: Some("He/Him".to_string()),
+            gender_identity: Some("non-binary".to_string()),
+            ..SelfIdentified::default()
+        };
+        assert_eq!(declared.gender().unwrap().argmax().0, 2);
+
+        let declared = SelfIdentified {
+            gender_identity: Some("prefer not to say".to_string()),
+            ..SelfIdentified::default()
+        };
+        assert!(declared.gender().is_none());
+    }
+}
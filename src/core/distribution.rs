@@ -0,0 +1,308 @@
+use std::{fmt, marker::PhantomData};
+
+use chrono::NaiveDate;
+
+use super::{AgeGroup, Ethnicity, Gender, scheme::ages_for_birth_year};
+
+const TOLERANCE: f64 = 1e-6;
+
+// LLM probabilities are written to about two decimals
+const ROUNDING: f64 = 0.01;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DistributionError {
+    NonFinite,
+    Negative(f64),
+    Empty,
+    ExceedsOne(f64),
+    BelowOne(f64),
+    WrongLength(usize),
+    UnknownCategory(String),
+}
+
+impl fmt::Display for DistributionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonFinite => write!(f, "probabilities must be finite"),
+            Self::Negative(p) => write!(f, "negative probability {p}"),
+            Self::Empty => write!(f, "distribution has no mass"),
+            Self::ExceedsOne(total) => write!(f, "probabilities sum to {total}, above 1"),
+            Self::BelowOne(total) => write!(f, "probabilities sum to {total}, below 1"),
+            Self::WrongLength(len) => write!(f, "unexpected number of outcomes: {len}"),
+            Self::UnknownCategory(label) => write!(f, "unknown category {label:?}"),
+        }
+    }
+}
+
+// Non-negative probabilities over the N outcomes of K that always sum to 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Distribution<const N: usize, K>([f64; N], PhantomData<K>);
+
+// male, female, other
+pub type GenderDistribution = Distribution<3, Gender>;
+
+// Indexed by AgeGroup
+pub type AgeDistribution = Distribution<7, AgeGroup>;
+
+// Indexed by Ethnicity
+pub type EthnicityDistribution = Distribution<7, Ethnicity>;
+
+impl<const N: usize, K> Distribution<N, K> {
+    // Validates and normalizes non-negative weights
+    pub fn new(weights: [f64; N]) -> Result<Self, DistributionError> {
+        if weights.iter().any(|p| !p.is_finite()) {
+            return Err(DistributionError::NonFinite);
+        }
+        if let Some(p) = weights.iter().find(|p| **p < 0.0) {
+            return Err(DistributionError::Negative(*p));
+        }
+
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            return Err(DistributionError::Empty);
+        }
+
+        Ok(Self(weights.map(|p| p / total), PhantomData))
+    }
+
+    pub fn from_slice(weights: &[f64]) -> Result<Self, DistributionError> {
+        let weights: [f64; N] = weights
+            .try_into()
+            .map_err(|_| DistributionError::WrongLength(weights.len()))?;
+        Self::new(weights)
+    }
+
+    pub fn one_hot(index: usize) -> Self {
+        let mut probs = [0.0; N];
+        probs[index] = 1.0;
+        Self(probs, PhantomData)
+    }
+
+    pub fn as_slice(&self) -> &[f64] {
+        &self.0
+    }
+
+    pub fn map(&self, f: impl Fn(f64) -> f64) -> Result<Self, DistributionError> {
+        Self::new(self.0.map(f))
+    }
+
+    pub fn argmax(&self) -> (usize, f64) {
+        self.0
+            .iter()
+            .copied()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap_or((0, 0.0))
+    }
+
+    pub fn normalized_entropy(&self) -> f64 {
+        normalized_entropy(&self.0)
+    }
+
+    pub fn kl(&self, other: &Self) -> f64 {
+        kl_divergence(&self.0, &other.0)
+    }
+
+    // Jensen-Shannon divergence in bits, bounded to [0, 1]
+    pub fn js(&self, other: &Self) -> f64 {
+        let mut mid = [0.0; N];
+        for (m, (p, q)) in mid.iter_mut().zip(self.0.iter().zip(&other.0)) {
+            *m = (p + q) / 2.0;
+        }
+        let mid = Self(mid, PhantomData);
+
+        (0.5 * self.kl(&mid) + 0.5 * other.kl(&mid)).clamp(0.0, 1.0)
+    }
+}
+
+impl GenderDistribution {
+    // Binary LLM output; whatever is left over goes to "other"
+    pub fn from_binary(male: f64, female: f64) -> Result<Self, DistributionError> {
+        let total = male + female;
+        if total > 1.0 + TOLERANCE {
+            return Err(DistributionError::ExceedsOne(total));
+        }
+        Self::new([male, female, (1.0 - total).max(0.0)])
+    }
+
+    pub fn male(&self) -> f64 {
+        self.0[0]
+    }
+
+    pub fn female(&self) -> f64 {
+        self.0[1]
+    }
+
+    pub fn other(&self) -> f64 {
+        self.0[2]
+    }
+}
+
+impl AgeDistribution {
+    pub fn from_group(group: AgeGroup) -> Self {
+        Self::one_hot(group as usize)
+    }
+
+    // Split between the two ages someone born in `year` can be today
+    pub fn from_birth_year(year: u16, today: NaiveDate) -> Self {
+        let mut probs = [0.0; 7];
+        for (age, p) in ages_for_birth_year(year, today) {
+            probs[AgeGroup::from_age(age) as usize] += p;
+        }
+        Self(probs, PhantomData)
+    }
+
+    pub fn get(&self, group: AgeGroup) -> f64 {
+        self.0[group as usize]
+    }
+
+    pub fn most_likely(&self) -> (AgeGroup, f64) {
+        let (index, p) = self.argmax();
+        (AgeGroup::ALL[index], p)
+    }
+}
+
+impl EthnicityDistribution {
+    // LLM output keyed by category label. Unknown labels are rejected, and so is
+    // a partial total: with no slot for the rest, renormalizing would inflate it
+    pub fn from_labels<'a>(
+        labels: impl IntoIterator<Item = (&'a str, f64)>,
+    ) -> Result<Self, DistributionError> {
+        let mut weights = [0.0; 7];
+        for (label, p) in labels {
+            let ethnicity = Ethnicity::parse(label)
+                .ok_or_else(|| DistributionError::UnknownCategory(label.to_string()))?;
+            weights[ethnicity as usize] += p;
+        }
+
+        let total: f64 = weights.iter().sum();
+        if total > 1.0 + TOLERANCE {
+            return Err(DistributionError::ExceedsOne(total));
+        }
+        if total < 1.0 - ROUNDING {
+            return Err(DistributionError::BelowOne(total));
+        }
+        Self::new(weights)
+    }
+
+    pub fn get(&self, ethnicity: Ethnicity) -> f64 {
+        self.0[ethnicity as usize]
+    }
+
+    pub fn most_likely(&self) -> (Ethnicity, f64) {
+        let (index, p) = self.argmax();
+        (Ethnicity::ALL[index], p)
+    }
+}
+
+// Natural-log entropy of normalized weights
+pub fn entropy(dist: &[f64]) -> f64 {
+    let total: f64 = dist.iter().sum();
+    if total <= 0.0 {
+        return 0.0;
+    }
+
+    dist.iter()
+        .map(|p| p / total)
+        .filter(|p| *p > 0.0)
+        .map(|p| -p * p.ln())
+        .sum()
+}
+
+// Entropy scaled to [0, 1] by the entropy of the uniform distribution
+pub fn normalized_entropy(dist: &[f64]) -> f64 {
+    if dist.len() < 2 {
+        return 0.0;
+    }
+    entropy(dist) / (dist.len() as f64).ln()
+}
+
+// Gap between the two most likely outcomes
+pub fn margin(dist: &[f64]) -> f64 {
+    let mut sorted = dist.to_vec();
+    sorted.sort_by(|a, b| b.total_cmp(a));
+    match sorted.as_slice() {
+        [first, second, ..] => first - second,
+        [first] => *first,
+        [] => 0.0,
+    }
+}
+
+// KL(p || q) in bits; infinite where q has no mass but p does
+pub fn kl_divergence(p: &[f64], q: &[f64]) -> f64 {
+    p.iter()
+        .zip(q)
+        .filter(|(p, _)| **p > 0.0)
+        .map(|(p, q)| {
+            if *q > 0.0 {
+                p * (p / q).log2()
+            } else {
+                f64::INFINITY
+            }
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_and_validates() {
+        let d = GenderDistribution::new([2.0, 6.0, 0.0]).unwrap();
+        assert_eq!(d.as_slice(), &[0.25, 0.75, 0.0]);
+
+        assert_eq!(
+            GenderDistribution::new([-0.1, 1.0, 0.0]),
+            Err(DistributionError::Negative(-0.1))
+        );
+        assert_eq!(
+            GenderDistribution::new([0.0; 3]),
+            Err(DistributionError::Empty)
+        );
+        assert!(GenderDistribution::from_binary(0.8, 0.7).is_err());
+    }
+
+    #[test]
+    fn binary_gender_fills_other() {
+        let d = GenderDistribution::from_binary(0.2, 0.7).unwrap();
+        assert!((d.other() - 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn entropy_argmax_and_kl() {
+        let uniform = AgeDistribution::new([1.0; 7]).unwrap();
+        assert!((uniform.normalized_entropy() - 1.0).abs() < 1e-9);
+
+        let adult = AgeDistribution::from_group(AgeGroup::Age25_34);
+        assert_eq!(adult.most_likely(), (AgeGroup::Age25_34, 1.0));
+        assert_eq!(adult.normalized_entropy(), 0.0);
+        assert!((adult.kl(&uniform) - 7f64.log2()).abs() < 1e-9);
+        assert!(uniform.kl(&adult).is_infinite());
+    }
+
+    #[test]
+    fn ethnicity_rejects_unknown_labels() {
+        let d =
+            EthnicityDistribution::from_labels([("european", 0.75), ("african", 0.25)]).unwrap();
+        assert_eq!(d.most_likely().0, Ethnicity::European);
+        assert!((d.get(Ethnicity::African) - 0.25).abs() < 1e-9);
+
+        assert_eq!(
+            EthnicityDistribution::from_labels([("european", 0.5), ("martian", 0.5)]),
+            Err(DistributionError::UnknownCategory("martian".to_string()))
+        );
+    }
+
+    #[test]
+    fn ethnicity_rejects_partial_totals() {
+        assert_eq!(
+            EthnicityDistribution::from_labels([("european", 0.3)]),
+            Err(DistributionError::BelowOne(0.3))
+        );
+
+        // Rounded model output is close enough
+        let d = EthnicityDistribution::from_labels([("european", 0.66), ("african", 0.33)]);
+        assert!(d.is_ok());
+    }
+}
@@ -0,0 +1,101 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use super::{AgeDistribution, AgeGroup, scheme::ages_for_birth_year};
+
+// Central credible interval reported alongside the expected age
+const INTERVAL: f64 = 0.8;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub struct AgeEstimate {
+    pub expected: f64,
+    pub low: f64,
+    pub high: f64,
+}
+
+impl AgeEstimate {
+    // With the birthday uniform over the year, the exact age today is uniform
+    // over the year leading up to `age + birthday_passed`
+    pub fn from_birth_year(year: u16, today: NaiveDate) -> Self {
+        let [(age, birthday_passed), _] = ages_for_birth_year(year, today);
+        let high = age as f64 + birthday_passed;
+        // Born this year: no older than the part of the year gone by
+        let low = (high - 1.0).max(0.0);
+        let width = high - low;
+        let tail = (1.0 - INTERVAL) / 2.0;
+
+        Self {
+            expected: low + width / 2.0,
+            low: low + width * tail,
+            high: high - width * tail,
+        }
+    }
+
+    // Treats each group as uniform over its years
+    pub fn from_distribution(age: &AgeDistribution) -> Self {
+        let expected = AgeGroup::ALL
+            .iter()
+            .map(|g| {
+                let (lo, hi) = g.years();
+                age.get(*g) * (lo as f64 + hi as f64 + 1.0) / 2.0
+            })
+            .sum();
+        let tail = (1.0 - INTERVAL) / 2.0;
+
+        Self {
+            expected,
+            low: quantile(age, tail),
+            high: quantile(age, 1.0 - tail),
+        }
+    }
+}
+
+fn quantile(age: &AgeDistribution, q: f64) -> f64 {
+    let mut cumulative = 0.0;
+    for group in AgeGroup::ALL {
+        let p = age.get(group);
+        let (lo, hi) = group.years();
+        if p > 0.0 && cumulative + p >= q {
+            let width = (hi - lo + 1) as f64;
+            return lo as f64 + width * (q - cumulative) / p;
+        }
+        cumulative += p;
+    }
+
+    AgeGroup::Age65Plus.years().1 as f64 + 1.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn birth_year_interval_covers_80_percent() {
+        // Halfway through the year: 35.5 to 36.5 years old
+        let today = NaiveDate::from_ymd_opt(2026, 7, 2).unwrap();
+        let estimate = AgeEstimate::from_birth_year(1990, today);
+        assert!((estimate.expected - 36.0).abs() < 0.01);
+        assert!((estimate.low - 35.6).abs() < 0.01);
+        assert!((estimate.high - 36.4).abs() < 0.01);
+    }
+
+    #[test]
+    fn current_birth_year_is_a_newborn() {
+        let today = NaiveDate::from_ymd_opt(2026, 7, 2).unwrap();
+        let estimate = AgeEstimate::from_birth_year(2026, today);
+        assert!(estimate.low >= 0.0 && estimate.high <= 0.51);
+
+        let age = AgeDistribution::from_birth_year(2026, today);
+        assert_eq!(age.get(AgeGroup::Under18), 1.0);
+    }
+
+    #[test]
+    fn distribution_interval_spans_groups() {
+        let age = AgeDistribution::new([0.0, 0.5, 0.5, 0.0, 0.0, 0.0, 0.0]).unwrap();
+        let estimate = AgeEstimate::from_distribution(&age);
+        assert!((estimate.expected - 25.75).abs() < 1e-9);
+        // 10th percentile is 20% into 18-24, 90th is 80% into 25-34
+        assert!((estimate.low - 19.4).abs() < 1e-9);
+        assert!((estimate.high - 33.0).abs() < 1e-9);
+    }
+}
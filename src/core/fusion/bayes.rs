@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+use super::{FusionStrategy, StrategyKind};
+use crate::core::Attribute;
+
+// Keeps a single source's hard zero from vetoing every other source
+const FLOOR: f64 = 1e-6;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct BayesianPrior {
+    pub gender: [f64; 3],
+    pub age: [f64; 7],
+}
+
+impl Default for BayesianPrior {
+    fn default() -> Self {
+        Self {
+            gender: [0.49, 0.49, 0.02],
+            age: [0.05, 0.15, 0.22, 0.20, 0.16, 0.13, 0.09],
+        }
+    }
+}
+
+impl BayesianPrior {
+    fn for_attribute(&self, attribute: Attribute, len: usize) -> Vec<f64> {
+        match attribute {
+            Attribute::Gender => self.gender.to_vec(),
+            Attribute::Age => self.age.to_vec(),
+            // No configured prior: flat
+            Attribute::Ethnicity | Attribute::Region | Attribute::Organization => {
+                vec![1.0 / len as f64; len]
+            }
+        }
+    }
+}
+
+// Product of experts: each source is independent evidence contributing its
+// log-likelihood ratio against the prior, scaled by its weight.
+pub struct BayesianStrategy {
+    prior: BayesianPrior,
+}
+
+impl BayesianStrategy {
+    pub fn new(prior: BayesianPrior) -> Self {
+        Self { prior }
+    }
+}
+
+impl FusionStrategy for BayesianStrategy {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::Bayesian
+    }
+
+    fn combine(&self, attribute: Attribute, distributions: &[(&[f64], f64)]) -> Vec<f64> {
+        let len = distributions.first().map_or(0, |(d, _)| d.len());
+        let prior = self.prior.for_attribute(attribute, len);
+
+        let log_odds: Vec<f64> = prior
+            .iter()
+            .enumerate()
+            .map(|(i, &p)| {
+                let p = p.max(FLOOR);
+                distributions.iter().fold(p.ln(), |acc, (dist, w)| {
+                    acc + w * (dist[i].max(FLOOR).ln() - p.ln())
+                })
+            })
+            .collect();
+
+        // Softmax back to probabilities
+        let max = log_odds.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let exps: Vec<f64> = log_odds.iter().map(|l| (l - max).exp()).collect();
+        let total: f64 = exps.iter().sum();
+        exps.into_iter().map(|e| e / total).collect()
+    }
+}
@@ -0,0 +1,23 @@
+use super::{FusionStrategy, StrategyKind};
+use crate::core::Attribute;
+
+pub struct MeanStrategy;
+
+impl FusionStrategy for MeanStrategy {
+    fn kind(&self) -> StrategyKind {
+        StrategyKind::Mean
+    }
+
+    fn combine(&self, _attribute: Attribute, distributions: &[(&[f64], f64)]) -> Vec<f64> {
+        let len = distributions.first().map_or(0, |(d, _)| d.len());
+        let total_weight: f64 = distributions.iter().map(|(_, w)| w).sum();
+
+        let mut result = vec![0.0; len];
+        for (dist, w) in distributions {
+            for (r, p) in result.iter_mut().zip(dist.iter()) {
+                *r += p * w / total_weight;
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,353 @@
+pub mod bayes;
+pub mod mean;
+pub mod organization;
+pub mod weight;
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+use super::{AgeEstimate, Attribute, InferenceSignal, SignalSource, distribution::Distribution};
+
+pub use bayes::{BayesianPrior, BayesianStrategy};
+pub use mean::MeanStrategy;
+pub use weight::*;
+
+pub trait FusionStrategy: Send + Sync {
+    fn kind(&self) -> StrategyKind;
+
+    fn combine(&self, attribute: Attribute, distributions: &[(&[f64], f64)]) -> Vec<f64>;
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum StrategyKind {
+    #[default]
+    Mean,
+    Bayesian,
+}
+
+impl StrategyKind {
+    pub fn build(self, prior: &BayesianPrior) -> Box<dyn FusionStrategy> {
+        match self {
+            Self::Mean => Box::new(MeanStrategy),
+            Self::Bayesian => Box::new(BayesianStrategy::new(prior.clone())),
+        }
+    }
+}
+
+pub fn fuse(
+    signals: Vec<InferenceSignal>,
+    weights: &FusionWeights,
+    strategy: &dyn FusionStrategy,
+) -> InferenceSignal {
+    // A lone signal still goes through the strategy, so weights and priors apply
+    let mut fused = match signals.len() {
+        0 => InferenceSignal::new(SignalSource::Local),
+        _ => blend(signals, weights, strategy),
+    };
+
+    // Age estimate: exact from a certain birth year, otherwise from the fused buckets
+    if fused.age_estimate.is_none() {
+        fused.age_estimate = fused.age.as_ref().map(AgeEstimate::from_distribution);
+    }
+
+    fused
+}
+
+fn blend(
+    signals: Vec<InferenceSignal>,
+    weights: &FusionWeights,
+    strategy: &dyn FusionStrategy,
+) -> InferenceSignal {
+    let mut result = InferenceSignal::new(SignalSource::Local);
+
+    // Gender, age and ethnicity: combine signals with data
+    result.gender = combine(
+        Attribute::Gender,
+        |s| s.gender.as_ref(),
+        &signals,
+        weights,
+        strategy,
+    );
+    result.age = combine(
+        Attribute::Age,
+        |s| s.age.as_ref(),
+        &signals,
+        weights,
+        strategy,
+    );
+    result.ethnicity = combine(
+        Attribute::Ethnicity,
+        |s| s.ethnicity.as_ref(),
+        &signals,
+        weights,
+        strategy,
+    );
+
+    // Region: align country keys; leftover mass stays unattributed
+    let region: Vec<_> = signals
+        .iter()
+        .filter(|s| s.has_region_signal())
+        .map(|s| (s, weights.for_source(&s.source).region))
+        .filter(|(_, w)| *w > 0.0)
+        .collect();
+    if !region.is_empty() {
+        let countries: BTreeSet<&String> =
+            region.iter().flat_map(|(s, _)| s.region.keys()).collect();
+        let aligned: Vec<_> = region
+            .iter()
+            .map(|(s, w)| {
+                let mut dist: Vec<f64> = countries
+                    .iter()
+                    .map(|c| s.region.get(*c).copied().unwrap_or(0.0))
+                    .collect();
+                dist.push((1.0 - dist.iter().sum::<f64>()).max(0.0));
+                (dist, *w)
+            })
+            .collect();
+        let dists: Vec<_> = aligned.iter().map(|(d, w)| (d.as_slice(), *w)).collect();
+        let probs = strategy.combine(Attribute::Region, &dists);
+        result.region = countries
+            .into_iter()
+            .cloned()
+            .zip(probs)
+            .filter(|(_, p)| *p > 0.0)
+            .collect();
+    }
+
+    // Birth year: self-reported, else first non-None (local is deterministic)
+    let declared_age = signals
+        .iter()
+        .find(|s| s.self_reported.contains(&Attribute::Age));
+    if let Some(dated) = declared_age
+        .filter(|s| s.birth_year.is_some())
+        .or_else(|| signals.iter().find(|s| s.birth_year.is_some()))
+    {
+        result.birth_year = dated.birth_year;
+        result.birth_year_certain = dated.birth_year_certain;
+    }
+    // Only a certain birth year carries an estimate; `fuse` derives the rest
+    // from the fused buckets
+    result.age_estimate = declared_age
+        .and_then(|s| s.age_estimate)
+        .or_else(|| signals.iter().find_map(|s| s.age_estimate));
+
+    // Organization: merged field by field
+    result.organization = organization::merge(&signals);
+
+    // Avatar: only vision reports it
+    result.is_human = signals.iter().find_map(|s| s.is_human);
+
+    result.self_reported = signals
+        .iter()
+        .flat_map(|s| s.self_reported.iter().copied())
+        .collect();
+
+    // Reasoning: combine all, tagged with the weight each source was fused at
+    result.reasoning = signals
+        .iter()
+        .flat_map(|s| s.reasoning.clone())
+        .map(|mut entry| {
+            if entry.weight.is_none() && !entry.is_error() {
+                entry.weight = entry
+                    .attribute
+                    .and_then(|a| weights.for_source(&entry.source).for_attribute(a));
+            }
+            entry
+        })
+        .collect();
+
+    // Latency: max (parallel execution)
+    result.latency_ms = signals.iter().map(|s| s.latency_ms).max().unwrap_or(0);
+
+    // Tokens: sum
+    let total: u32 = signals.iter().filter_map(|s| s.tokens_used).sum();
+    result.tokens_used = if total > 0 { Some(total) } else { None };
+
+    result
+}
+
+fn combine<const N: usize, K: Clone>(
+    attribute: Attribute,
+    select: fn(&InferenceSignal) -> Option<&Distribution<N, K>>,
+    signals: &[InferenceSignal],
+    weights: &FusionWeights,
+    strategy: &dyn FusionStrategy,
+) -> Option<Distribution<N, K>> {
+    // What the person says about themselves is not up for a vote
+    if let Some(declared) = signals
+        .iter()
+        .filter(|s| s.self_reported.contains(&attribute))
+        .find_map(select)
+    {
+        return Some(declared.clone());
+    }
+
+    let dists: Vec<_> = signals
+        .iter()
+        .filter_map(|s| {
+            let w = weights.for_source(&s.source).for_attribute(attribute)?;
+            Some((select(s)?.as_slice(), w))
+        })
+        .filter(|(_, w)| *w > 0.0)
+        .collect();
+    if dists.is_empty() {
+        return None;
+    }
+
+    Distribution::from_slice(&strategy.combine(attribute, &dists)).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{
+        AgeDistribution, AgeGroup, Ethnicity, EthnicityDistribution, GenderDistribution,
+    };
+
+    #[test]
+    fn weights_gender_by_source() {
+        let mut onomast = InferenceSignal::new(SignalSource::Onomastic);
+        onomast.gender = Some(GenderDistribution::one_hot(1));
+
+        let mut vision = InferenceSignal::new(SignalSource::Vision);
+        vision.gender = Some(GenderDistribution::one_hot(0));
+
+        let weights = FusionWeights {
+            onomastic: SourceWeights::uniform(0.75),
+            vision: SourceWeights::uniform(0.25),
+            ..FusionWeights::default()
+        };
+
+        let fused = fuse(vec![onomast, vision], &weights, &MeanStrategy);
+        let gender = fused.gender.unwrap();
+        assert!((gender.female() - 0.75).abs() < 1e-9);
+        assert!((gender.male() - 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn zero_weight_drops_source() {
+        let mut local = InferenceSignal::new(SignalSource::Local);
+        local.age = Some(AgeDistribution::from_group(AgeGroup::Age25_34));
+
+        let mut vision = InferenceSignal::new(SignalSource::Vision);
+        vision.age = Some(AgeDistribution::from_group(AgeGroup::Age45_54));
+
+        let weights = FusionWeights {
+            vision: SourceWeights::uniform(0.0),
+            ..FusionWeights::default()
+        };
+
+        let fused = fuse(vec![local, vision], &weights, &MeanStrategy);
+        let age = fused.age.unwrap();
+        assert_eq!(age.get(AgeGroup::Age25_34), 1.0);
+        assert_eq!(age.get(AgeGroup::Age45_54), 0.0);
+    }
+
+    #[test]
+    fn bayesian_agreement_strengthens() {
+        let weak_female = |source| {
+            let mut s = InferenceSignal::new(source);
+            s.gender = GenderDistribution::new([0.3, 0.7, 0.0]).ok();
+            s
+        };
+        let signals = vec![
+            weak_female(SignalSource::Onomastic),
+            weak_female(SignalSource::Vision),
+        ];
+        let weights = FusionWeights {
+            onomastic: SourceWeights::uniform(1.0),
+            vision: SourceWeights::uniform(1.0),
+            ..FusionWeights::default()
+        };
+
+        let mean = fuse(signals.clone(), &weights, &MeanStrategy);
+        let bayes = fuse(
+            signals,
+            &weights,
+            &BayesianStrategy::new(BayesianPrior::default()),
+        );
+
+        assert!((mean.gender.unwrap().female() - 0.7).abs() < 1e-9);
+        assert!(bayes.gender.unwrap().female() > 0.8);
+    }
+
+    #[test]
+    fn fuses_ethnicity_distributions() {
+        let mut onomast = InferenceSignal::new(SignalSource::Onomastic);
+        onomast.ethnicity =
+            EthnicityDistribution::from_labels([("european", 0.8), ("african", 0.2)]).ok();
+
+        let mut vision = InferenceSignal::new(SignalSource::Vision);
+        vision.ethnicity =
+            EthnicityDistribution::from_labels([("european", 0.4), ("african", 0.6)]).ok();
+
+        let fused = fuse(
+            vec![onomast, vision],
+            &FusionWeights::default(),
+            &MeanStrategy,
+        );
+        let ethnicity = fused.ethnicity.unwrap();
+        // onomast 0.8 vs vision 0.6: (0.8 * 0.8 + 0.6 * 0.4) / 1.4
+        assert!((ethnicity.get(Ethnicity::European) - 0.88 / 1.4).abs() < 1e-9);
+        assert_eq!(ethnicity.most_likely().0, Ethnicity::European);
+    }
+
+    #[test]
+    fn single_signal_goes_through_strategy() {
+        let mut vision = InferenceSignal::new(SignalSource::Vision);
+        vision.gender = GenderDistribution::new([0.1, 0.9, 0.0]).ok();
+
+        let bayes = BayesianStrategy::new(BayesianPrior::default());
+        let fused = fuse(vec![vision.clone()], &FusionWeights::default(), &bayes);
+        // Vision's 0.6 weight pulls the answer back toward the prior
+        let female = fused.gender.unwrap().female();
+        assert!(female < 0.9 && female > 0.49);
+
+        let weights = FusionWeights {
+            vision: SourceWeights::uniform(0.0),
+            ..FusionWeights::default()
+        };
+        assert!(fuse(vec![vision], &weights, &MeanStrategy).gender.is_none());
+    }
+
+    #[test]
+    fn uncertain_birth_year_estimate_comes_from_fused_buckets() {
+        let mut local = InferenceSignal::new(SignalSource::Local);
+        local.birth_year = Some(1990);
+        local.age = AgeDistribution::new([0.0, 0.1, 0.2, 0.6, 0.1, 0.0, 0.0]).ok();
+
+        let mut vision = InferenceSignal::new(SignalSource::Vision);
+        vision.age = Some(AgeDistribution::from_group(AgeGroup::Age25_34));
+
+        let fused = fuse(
+            vec![local, vision],
+            &FusionWeights::default(),
+            &MeanStrategy,
+        );
+        let estimate = fused.age_estimate.unwrap();
+        assert_eq!(
+            estimate,
+            AgeEstimate::from_distribution(&fused.age.unwrap())
+        );
+        assert!(estimate.high - estimate.low > 10.0);
+    }
+
+    #[test]
+    fn self_reported_gender_is_authoritative() {
+        let mut declared = InferenceSignal::new(SignalSource::SelfReported);
+        declared.gender = Some(GenderDistribution::one_hot(2));
+        declared.self_reported.push(Attribute::Gender);
+
+        let mut onomast = InferenceSignal::new(SignalSource::Onomastic);
+        onomast.gender = Some(GenderDistribution::one_hot(1));
+
+        let fused = fuse(
+            vec![onomast, declared],
+            &FusionWeights::default(),
+            &MeanStrategy,
+        );
+        assert_eq!(fused.gender, Some(GenderDistribution::one_hot(2)));
+        assert_eq!(fused.self_reported, vec![Attribute::Gender]);
+    }
+}
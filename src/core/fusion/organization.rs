@@ -0,0 +1,120 @@
+use crate::core::{
+    FieldCandidate, FieldConflict, FieldProvenance, InferenceSignal, OrganizationIntelligence,
+    SignalSource, normalize_domain,
+};
+
+// Merges each field independently: the value most sources agree on wins,
+// ties go to the earlier source, and disagreements are kept as conflicts
+pub fn merge(signals: &[InferenceSignal]) -> Option<OrganizationIntelligence> {
+    let orgs: Vec<_> = signals
+        .iter()
+        .filter_map(|s| Some((&s.source, s.organization.as_ref()?)))
+        .collect();
+    if orgs.is_empty() {
+        return None;
+    }
+
+    let mut merged = OrganizationIntelligence::default();
+    let field = |get: fn(&OrganizationIntelligence) -> Option<String>| -> Vec<FieldCandidate> {
+        orgs.iter()
+            .filter_map(|(source, org)| {
+                Some(FieldCandidate {
+                    source: (*source).clone(),
+                    value: get(org)?,
+                })
+            })
+            .collect()
+    };
+
+    let domain = field(|o| Some(normalize_domain(&o.domain)));
+    let name = field(|o| o.name.clone());
+    let category = field(|o| o.category.clone());
+    let employee_count = field(|o| o.employee_count.clone());
+    let employee_count_source = field(|o| o.employee_count_source.clone());
+    let hq_country = field(|o| o.hq_country.clone());
+
+    merged.domain = pick(&mut merged, "domain", domain).unwrap_or_default();
+    merged.name = pick(&mut merged, "name", name);
+    merged.category = pick(&mut merged, "category", category);
+    merged.employee_count = pick(&mut merged, "employee_count", employee_count);
+    merged.employee_count_source =
+        pick(&mut merged, "employee_count_source", employee_count_source);
+    merged.hq_country = pick(&mut merged, "hq_country", hq_country);
+
+    Some(merged)
+}
+
+fn pick(
+    org: &mut OrganizationIntelligence,
+    field: &str,
+    candidates: Vec<FieldCandidate>,
+) -> Option<String> {
+    // Group case-insensitively, keeping the first spelling seen
+    let mut groups: Vec<(&str, Vec<SignalSource>)> = Vec::new();
+    for candidate in &candidates {
+        match groups
+            .iter_mut()
+            .find(|(value, _)| value.eq_ignore_ascii_case(candidate.value.trim()))
+        {
+            Some((_, sources)) => sources.push(candidate.source.clone()),
+            None => groups.push((candidate.value.trim(), vec![candidate.source.clone()])),
+        }
+    }
+
+    // max_by_key keeps the last maximum, so walk backwards to favour earlier sources
+    let (value, sources) = groups.iter().rev().max_by_key(|(_, s)| s.len())?.clone();
+    let value = value.to_string();
+
+    org.provenance.insert(
+        field.to_string(),
+        FieldProvenance {
+            confidence: sources.len() as f64 / candidates.len() as f64,
+            sources,
+        },
+    );
+    if groups.len() > 1 {
+        org.conflicts.push(FieldConflict {
+            field: field.to_string(),
+            candidates,
+        });
+    }
+
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_fields_and_reports_conflicts() {
+        let mut local = InferenceSignal::new(SignalSource::Local);
+        local.organization = Some(OrganizationIntelligence::new("Vogue.com"));
+
+        let mut domain = InferenceSignal::new(SignalSource::Domain);
+        domain.organization = Some(OrganizationIntelligence {
+            name: Some("Vogue".to_string()),
+            hq_country: Some("US".to_string()),
+            ..OrganizationIntelligence::new("vogue.com")
+        });
+
+        let mut vision = InferenceSignal::new(SignalSource::Vision);
+        vision.organization = Some(OrganizationIntelligence {
+            category: Some("Media / Fashion".to_string()),
+            hq_country: Some("GB".to_string()),
+            ..OrganizationIntelligence::new("www.vogue.com")
+        });
+
+        let org = merge(&[local, domain, vision]).unwrap();
+        assert_eq!(org.domain, "vogue.com");
+        assert_eq!(org.name.as_deref(), Some("Vogue"));
+        assert_eq!(org.category.as_deref(), Some("Media / Fashion"));
+        assert_eq!(org.provenance["domain"].confidence, 1.0);
+
+        // Tie goes to the earlier source
+        assert_eq!(org.hq_country.as_deref(), Some("US"));
+        assert_eq!(org.provenance["hq_country"].confidence, 0.5);
+        assert_eq!(org.conflicts.len(), 1);
+        assert_eq!(org.conflicts[0].field, "hq_country");
+    }
+}
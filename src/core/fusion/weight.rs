@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::{Attribute, SignalSource};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SourceWeights {
+    pub gender: f64,
+    pub age: f64,
+    pub ethnicity: f64,
+    pub region: f64,
+}
+
+impl SourceWeights {
+    pub const fn uniform(weight: f64) -> Self {
+        Self {
+            gender: weight,
+            age: weight,
+            ethnicity: weight,
+            region: weight,
+        }
+    }
+
+    pub fn for_attribute(&self, attribute: Attribute) -> Option<f64> {
+        match attribute {
+            Attribute::Gender => Some(self.gender),
+            Attribute::Age => Some(self.age),
+            Attribute::Ethnicity => Some(self.ethnicity),
+            Attribute::Region => Some(self.region),
+            Attribute::Organization => None,
+        }
+    }
+}
+
+impl Default for SourceWeights {
+    fn default() -> Self {
+        Self::uniform(1.0)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct FusionWeights {
+    pub local: SourceWeights,
+    pub vision: SourceWeights,
+    pub onomastic: SourceWeights,
+    pub domain: SourceWeights,
+}
+
+// Self-reported attributes bypass weighting (see `combine`) and rules run after fusion
+const UNWEIGHTED: SourceWeights = SourceWeights::uniform(1.0);
+
+impl FusionWeights {
+    pub fn for_source(&self, source: &SignalSource) -> &SourceWeights {
+        match source {
+            SignalSource::SelfReported | SignalSource::Rules => &UNWEIGHTED,
+            SignalSource::Local => &self.local,
+            SignalSource::Vision => &self.vision,
+            SignalSource::Onomastic => &self.onomastic,
+            SignalSource::Domain => &self.domain,
+        }
+    }
+}
+
+impl Default for FusionWeights {
+    fn default() -> Self {
+        Self {
+            // Birth years from the email are deterministic
+            local: SourceWeights::uniform(1.0),
+            vision: SourceWeights {
+                gender: 0.6,
+                age: 0.6,
+                ethnicity: 0.6,
+                region: 0.6,
+            },
+            onomastic: SourceWeights {
+                gender: 0.8,
+                age: 0.5,
+                ethnicity: 0.8,
+                region: 0.5,
+            },
+            domain: SourceWeights {
+                region: 0.6,
+                ..SourceWeights::uniform(1.0)
+            },
+        }
+    }
+}
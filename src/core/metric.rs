@@ -1,13 +1,44 @@
-use super::r#type::SignalSource;
+use super::{
+    agreement::Disagreement,
+    fusion::{FusionWeights, StrategyKind},
+    minor::Restriction,
+    r#type::SignalSource,
+};
 use serde::Serialize;
 
+use crate::agent::AgentError;
+
 #[derive(Debug, Clone, Serialize)]
 pub struct SourceMetrics {
     pub source: SignalSource,
+    #[serde(flatten)]
+    pub status: SourceStatus,
     pub latency_ms: u64,
     pub tokens_used: Option<u32>,
+    pub attempts: Option<u32>,
     pub contributed: Vec<String>,
     pub confidence: f64,
+    pub sampling: Option<SamplingMetrics>,
+}
+
+// Tells an outage apart from a source that simply found nothing
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SourceStatus {
+    Ok,
+    NoEvidence,
+    Failed { error: AgentError },
+}
+
+// Self-consistency sampling behind a source's answer
+#[derive(Debug, Clone, Serialize)]
+pub struct SamplingMetrics {
+    pub requested: u32,
+    pub parsed: u32,
+    // Mean Jensen-Shannon divergence of the samples from their average, in bits
+    pub spread: f64,
+    // Tokens spent beyond a single-sample call
+    pub extra_tokens: u32,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -18,8 +49,20 @@ pub struct InferenceMetrics {
     pub sources_used: Vec<SourceMetrics>,
     pub sources_agreed: bool,
     pub fusion_confidence: f64,
+    pub disagreements: Vec<Disagreement>,
+    pub fusion_strategy: StrategyKind,
+    pub fusion_weights: FusionWeights,
+    pub calibration_version: Option<String>,
     pub edge_case: bool,
+    pub restriction: Option<Restriction>,
     pub total_tokens: u32,
     pub estimated_cost_usd: f64,
     pub total_latency_ms: u64,
 }
+
+impl InferenceMetrics {
+    pub fn with_restriction(mut self, restriction: Option<Restriction>) -> Self {
+        self.restriction = restriction;
+        self
+    }
+}
@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+use super::{AgeGroup, Attribute, InferenceSignal, reasoning::EvidenceKind};
+
+// Withholds demographics when the person is plausibly under 18
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct MinorPolicy {
+    // Under-18 probability at or above which the response is restricted
+    pub threshold: f64,
+    pub suppress: Vec<Attribute>,
+}
+
+impl Default for MinorPolicy {
+    fn default() -> Self {
+        Self {
+            threshold: 0.3,
+            suppress: vec![
+                Attribute::Gender,
+                Attribute::Ethnicity,
+                Attribute::Organization,
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Restriction {
+    pub under_18: f64,
+    pub threshold: f64,
+    pub suppressed: Vec<Attribute>,
+}
+
+impl MinorPolicy {
+    pub fn apply(&self, signal: &mut InferenceSignal) -> Option<Restriction> {
+        let under_18 = signal.age.as_ref()?.get(AgeGroup::Under18);
+        if under_18 < self.threshold {
+            return None;
+        }
+
+        self.scrub(signal);
+
+        Some(Restriction {
+            under_18,
+            threshold: self.threshold,
+            suppressed: self.suppress.clone(),
+        })
+    }
+
+    // Removes every trace of the suppressed attributes; also run on the source
+    // signals so metrics cannot leak what the response withholds
+    pub fn scrub(&self, signal: &mut InferenceSignal) {
+        for attribute in &self.suppress {
+            match attribute {
+                Attribute::Gender => signal.gender = None,
+                Attribute::Ethnicity => signal.ethnicity = None,
+                Attribute::Organization => signal.organization = None,
+                Attribute::Region => signal.region.clear(),
+                Attribute::Age => {
+                    signal.age = None;
+                    signal.age_estimate = None;
+                    signal.birth_year = None;
+                    signal.birth_year_certain = false;
+                }
+            }
+        }
+
+        // Unattributed LLM narrative may discuss any of the suppressed attributes
+        signal.reasoning.retain(|entry| match entry.attribute {
+            Some(attribute) => !self.suppress.contains(&attribute),
+            None => entry.kind != EvidenceKind::Evidence,
+        });
+        signal.self_reported.retain(|a| !self.suppress.contains(a));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{
+        AgeDistribution, GenderDistribution, OrganizationIntelligence, SignalSource,
+    };
+
+    #[test]
+    fn suppresses_demographics_for_likely_minors() {
+        let mut signal = InferenceSignal::new(SignalSource::Local);
+        signal.gender = Some(GenderDistribution::one_hot(1));
+        signal.organization = Some(OrganizationIntelligence::new("school.edu"));
+        signal.age = AgeDistribution::new([0.4, 0.6, 0.0, 0.0, 0.0, 0.0, 0.0]).ok();
+        signal.explain(Some(Attribute::Gender), "test", "Female name.");
+        signal.explain(Some(Attribute::Age), "test", "Young.");
+
+        let restriction = MinorPolicy::default().apply(&mut signal).unwrap();
+        assert_eq!(restriction.under_18, 0.4);
+        assert!(signal.gender.is_none() && signal.organization.is_none());
+        assert!(signal.age.is_some());
+        assert_eq!(signal.reasoning.len(), 1);
+
+        let mut adult = InferenceSignal::new(SignalSource::Local);
+        adult.age = Some(AgeDistribution::from_group(AgeGroup::Age25_34));
+        assert!(MinorPolicy::default().apply(&mut adult).is_none());
+    }
+}
@@ -1,10 +1,28 @@
+pub mod agreement;
+pub mod clock;
+pub mod declared;
+pub mod distribution;
+pub mod estimate;
 pub mod fusion;
 pub mod metric;
+pub mod minor;
+pub mod policy;
+pub mod reasoning;
+pub mod scheme;
 pub mod signal;
 pub mod r#type;
 
+pub use agreement::Agreement;
+pub use clock::{Clock, FixedClock, SystemClock};
+pub use declared::SelfIdentified;
+pub use distribution::{AgeDistribution, EthnicityDistribution, GenderDistribution};
+pub use estimate::AgeEstimate;
 pub use metric::*;
+pub use minor::MinorPolicy;
+pub use policy::{Abstention, ConfidencePolicy};
+pub use reasoning::ReasoningEntry;
+pub use scheme::{AgeScheme, SchemedAge};
 pub use signal::*;
 pub use r#type::*;
 
-pub use fusion::fuse;
+pub use fusion::{BayesianPrior, FusionStrategy, FusionWeights, StrategyKind, fuse};
@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    Attribute, Confidence,
+    distribution::{margin, normalized_entropy},
+};
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct ConfidenceBands {
+    pub strong: f64,
+    pub medium: f64,
+}
+
+impl Default for ConfidenceBands {
+    fn default() -> Self {
+        Self {
+            strong: 0.8,
+            medium: 0.5,
+        }
+    }
+}
+
+impl ConfidenceBands {
+    pub fn classify(&self, p: f64) -> Confidence {
+        match p {
+            p if p >= self.strong => Confidence::Strong,
+            p if p >= self.medium => Confidence::Medium,
+            p if p > 0.0 => Confidence::Low,
+            _ => Confidence::None,
+        }
+    }
+}
+
+// Decides when a distribution is decisive enough to report a label
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct ConfidencePolicy {
+    pub bands: ConfidenceBands,
+    // Minimum gap between the two most likely outcomes
+    pub min_margin: f64,
+    // Maximum entropy, normalized to [0, 1]
+    pub max_entropy: f64,
+}
+
+impl Default for ConfidencePolicy {
+    fn default() -> Self {
+        Self {
+            bands: ConfidenceBands::default(),
+            min_margin: 0.0,
+            max_entropy: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AbstentionReason {
+    LowMargin,
+    HighEntropy,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Abstention {
+    pub attribute: Attribute,
+    pub reason: AbstentionReason,
+    // The margin or normalized entropy that failed the rule
+    pub value: f64,
+}
+
+impl ConfidencePolicy {
+    pub fn assess(&self, attribute: Attribute, probs: &[f64]) -> Result<Confidence, Abstention> {
+        let abstain = |reason, value| Abstention {
+            attribute,
+            reason,
+            value,
+        };
+
+        let entropy = normalized_entropy(probs);
+        if entropy > self.max_entropy {
+            return Err(abstain(AbstentionReason::HighEntropy, entropy));
+        }
+
+        let margin = margin(probs);
+        if margin < self.min_margin {
+            return Err(abstain(AbstentionReason::LowMargin, margin));
+        }
+
+        let top = probs.iter().copied().fold(0.0, f64::max);
+        Ok(self.bands.classify(top))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::GenderDistribution;
+
+    #[test]
+    fn abstains_on_flat_distributions() {
+        let policy = ConfidencePolicy {
+            min_margin: 0.2,
+            max_entropy: 0.9,
+            ..ConfidencePolicy::default()
+        };
+
+        let close = GenderDistribution::new([0.45, 0.55, 0.0]).unwrap();
+        let err = policy
+            .assess(Attribute::Gender, close.as_slice())
+            .unwrap_err();
+        assert_eq!(err.reason, AbstentionReason::LowMargin);
+
+        let flat = GenderDistribution::new([0.4, 0.35, 0.25]).unwrap();
+        let err = policy
+            .assess(Attribute::Gender, flat.as_slice())
+            .unwrap_err();
+        assert_eq!(err.reason, AbstentionReason::HighEntropy);
+
+        let decisive = GenderDistribution::new([0.1, 0.9, 0.0]).unwrap();
+        assert_eq!(
+            policy
+                .assess(Attribute::Gender, decisive.as_slice())
+                .unwrap(),
+            Confidence::Strong
+        );
+    }
+}
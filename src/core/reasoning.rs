@@ -0,0 +1,137 @@
+use serde::Serialize;
+use std::fmt;
+
+use super::{Attribute, SignalSource};
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EvidenceKind {
+    Evidence,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReasoningEntry {
+    pub source: SignalSource,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attribute: Option<Attribute>,
+
+    pub kind: EvidenceKind,
+    pub code: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+
+    pub message: String,
+}
+
+impl ReasoningEntry {
+    pub fn new(
+        source: SignalSource,
+        kind: EvidenceKind,
+        attribute: Option<Attribute>,
+        code: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            source,
+            attribute,
+            kind,
+            code: code.into(),
+            weight: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.kind == EvidenceKind::Error
+    }
+}
+
+impl fmt::Display for ReasoningEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+// Evidence split across attributes is shown once
+pub fn render(entries: &[ReasoningEntry]) -> Vec<String> {
+    let mut rendered: Vec<String> = Vec::new();
+    for (i, entry) in entries.iter().enumerate() {
+        let repeated = entries[..i].iter().any(|e| {
+            e.source == entry.source && e.code == entry.code && e.message == entry.message
+        });
+        if !repeated {
+            rendered.push(entry.to_string());
+        }
+    }
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::InferenceSignal;
+
+    fn signal() -> InferenceSignal {
+        let mut signal = InferenceSignal::new(SignalSource::Onomastic);
+        signal.explain_each(
+            &[Attribute::Gender, Attribute::Ethnicity],
+            "onomastic_analysis",
+            "Common Italian female name.",
+        );
+        signal.warn(Some(Attribute::Ethnicity), "output_repaired", "Repaired.");
+        signal.fail("timeout", "Request timed out");
+        signal
+    }
+
+    #[test]
+    fn filtering_by_attribute_keeps_shared_evidence() {
+        let signal = signal();
+        let codes = |attribute| {
+            signal
+                .reasoning
+                .iter()
+                .filter(|e| e.attribute == Some(attribute))
+                .map(|e| e.code.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(codes(Attribute::Gender), ["onomastic_analysis"]);
+        assert_eq!(
+            codes(Attribute::Ethnicity),
+            ["onomastic_analysis", "output_repaired"]
+        );
+        assert!(codes(Attribute::Age).is_empty());
+    }
+
+    #[test]
+    fn errors_stay_apart_from_evidence() {
+        let signal = signal();
+        let errors: Vec<_> = signal.reasoning.iter().filter(|e| e.is_error()).collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, "timeout");
+        assert!(errors[0].attribute.is_none());
+
+        let evidence = signal
+            .reasoning
+            .iter()
+            .filter(|e| e.kind == EvidenceKind::Evidence)
+            .count();
+        assert_eq!(evidence, 2);
+    }
+
+    #[test]
+    fn renders_split_evidence_once() {
+        assert_eq!(
+            render(&signal().reasoning),
+            [
+                "Common Italian female name.",
+                "Repaired.",
+                "Request timed out"
+            ]
+        );
+    }
+}
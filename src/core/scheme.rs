@@ -0,0 +1,216 @@
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use super::{AgeGroup, InferenceSignal};
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct AgeBucket {
+    pub label: String,
+    pub min: u16,
+    // Inclusive; open-ended when absent
+    pub max: Option<u16>,
+}
+
+impl AgeBucket {
+    fn contains(&self, age: i32) -> bool {
+        age >= self.min as i32 && self.max.is_none_or(|max| age <= max as i32)
+    }
+
+    fn overlap(&self, (lo, hi): (u16, u16)) -> u16 {
+        let hi = hi.min(self.max.unwrap_or(u16::MAX));
+        let lo = lo.max(self.min);
+        (hi + 1).saturating_sub(lo)
+    }
+}
+
+// Ordered, contiguous age buckets from 0 up, the last open-ended, e.g.
+// 0-17 / 18-29 / 30-44 / 45+. Gaps or overlaps would lose or double-count
+// mass when re-binning, so they are rejected when the config loads.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(try_from = "Vec<AgeBucket>")]
+pub struct AgeScheme {
+    pub buckets: Vec<AgeBucket>,
+}
+
+impl TryFrom<Vec<AgeBucket>> for AgeScheme {
+    type Error = String;
+
+    fn try_from(buckets: Vec<AgeBucket>) -> Result<Self, Self::Error> {
+        let (Some(first), Some(last)) = (buckets.first(), buckets.last()) else {
+            return Err("an age scheme needs at least one bucket".to_string());
+        };
+        if first.min != 0 {
+            return Err(format!(
+                "bucket {:?} starts at {}; the first bucket must start at 0",
+                first.label, first.min
+            ));
+        }
+        if let Some(max) = last.max {
+            return Err(format!(
+                "bucket {:?} ends at {max}; the last bucket must be open-ended",
+                last.label
+            ));
+        }
+
+        for pair in buckets.windows(2) {
+            let (a, b) = (&pair[0], &pair[1]);
+            let Some(max) = a.max else {
+                return Err(format!(
+                    "only the last bucket can be open-ended, not {:?}",
+                    a.label
+                ));
+            };
+            if max < a.min {
+                return Err(format!("bucket {:?} ends before it starts", a.label));
+            }
+            if b.min != max + 1 {
+                let problem = if b.min > max + 1 {
+                    "leave a gap"
+                } else {
+                    "overlap"
+                };
+                return Err(format!("buckets {:?} and {:?} {problem}", a.label, b.label));
+            }
+        }
+
+        Ok(Self { buckets })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BucketProbability {
+    pub label: String,
+    pub probability: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemedAge {
+    pub scheme: String,
+    pub buckets: Vec<BucketProbability>,
+}
+
+impl AgeScheme {
+    // A certain birth year maps exactly; otherwise the internal groups are
+    // re-binned, keeping the doubt a guessed year was given
+    pub fn bin(&self, signal: &InferenceSignal, today: NaiveDate) -> Vec<BucketProbability> {
+        let mut probs = vec![0.0; self.buckets.len()];
+
+        if let Some(year) = signal.birth_year.filter(|_| signal.birth_year_certain) {
+            for (age, p) in ages_for_birth_year(year, today) {
+                if let Some(i) = self.buckets.iter().position(|b| b.contains(age)) {
+                    probs[i] += p;
+                }
+            }
+        } else if let Some(age) = &signal.age {
+            // Each group's mass is spread uniformly over the years it covers
+            for group in AgeGroup::ALL {
+                let span = group.years();
+                let width = (span.1 - span.0 + 1) as f64;
+                for (bucket, p) in self.buckets.iter().zip(probs.iter_mut()) {
+                    *p += age.get(group) * bucket.overlap(span) as f64 / width;
+                }
+            }
+        }
+
+        let total: f64 = probs.iter().sum();
+        if total <= 0.0 {
+            return Vec::new();
+        }
+
+        self.buckets
+            .iter()
+            .zip(probs)
+            .map(|(bucket, p)| BucketProbability {
+                label: bucket.label.clone(),
+                probability: p / total,
+            })
+            .collect()
+    }
+}
+
+// The two ages someone born in `year` can be today, weighted by how much of
+// the year has passed. Someone born this year can only be 0.
+pub fn ages_for_birth_year(year: u16, today: NaiveDate) -> [(i32, f64); 2] {
+    let days_in_year = if today.leap_year() { 366.0 } else { 365.0 };
+    let birthday_passed = today.ordinal() as f64 / days_in_year;
+    let age = today.year() - year as i32;
+
+    [
+        (age, birthday_passed),
+        ((age - 1).max(0), 1.0 - birthday_passed),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{AgeDistribution, SignalSource};
+
+    fn scheme() -> AgeScheme {
+        serde_json::from_str(
+            r#"[
+                {"label": "under 18", "min": 0, "max": 17},
+                {"label": "18-29", "min": 18, "max": 29},
+                {"label": "30-44", "min": 30, "max": 44},
+                {"label": "45+", "min": 45}
+            ]"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn rebins_proportionally() {
+        let mut signal = InferenceSignal::new(SignalSource::Onomastic);
+        signal.age = Some(AgeDistribution::from_group(AgeGroup::Age25_34));
+
+        let today = NaiveDate::from_ymd_opt(2026, 6, 1).unwrap();
+        let buckets = scheme().bin(&signal, today);
+        assert!((buckets[1].probability - 0.5).abs() < 1e-9);
+        assert!((buckets[2].probability - 0.5).abs() < 1e-9);
+        assert_eq!(buckets[3].probability, 0.0);
+    }
+
+    #[test]
+    fn birth_year_maps_exactly() {
+        let mut signal = InferenceSignal::new(SignalSource::Local);
+        signal.birth_year = Some(1990);
+        signal.birth_year_certain = true;
+        signal.age = Some(AgeDistribution::from_group(AgeGroup::Age35_44));
+
+        // Turning 36 or still 35: both in 30-44
+        let today = NaiveDate::from_ymd_opt(2026, 6, 1).unwrap();
+        let buckets = scheme().bin(&signal, today);
+        assert_eq!(buckets[2].label, "30-44");
+        assert!((buckets[2].probability - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn guessed_birth_year_is_rebinned() {
+        let mut signal = InferenceSignal::new(SignalSource::Local);
+        signal.birth_year = Some(1990);
+        signal.age = AgeDistribution::new([0.0, 0.1, 0.2, 0.6, 0.1, 0.0, 0.0]).ok();
+
+        let today = NaiveDate::from_ymd_opt(2026, 6, 1).unwrap();
+        let buckets = scheme().bin(&signal, today);
+        assert!(buckets[2].probability < 0.9);
+        assert!(buckets[3].probability > 0.0);
+    }
+
+    #[test]
+    fn rejects_gaps_overlaps_and_partial_cover() {
+        let parse = |json| {
+            serde_json::from_str::<AgeScheme>(json)
+                .unwrap_err()
+                .to_string()
+        };
+
+        let gap = r#"[{"label": "a", "min": 0, "max": 17}, {"label": "b", "min": 20}]"#;
+        assert!(parse(gap).contains("leave a gap"));
+        let overlap = r#"[{"label": "a", "min": 0, "max": 30}, {"label": "b", "min": 25}]"#;
+        assert!(parse(overlap).contains("overlap"));
+        let late = r#"[{"label": "a", "min": 18}]"#;
+        assert!(parse(late).contains("must start at 0"));
+        let closed = r#"[{"label": "a", "min": 0, "max": 64}]"#;
+        assert!(parse(closed).contains("open-ended"));
+    }
+}
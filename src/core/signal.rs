@@ -1,6 +1,15 @@
-use crate::core::OrganizationIntelligence;
+use std::collections::BTreeMap;
 
-use super::r#type::SignalSource;
+use crate::{agent::AgentError, core::OrganizationIntelligence};
+
+use super::{
+    declared::SelfIdentified,
+    distribution::{AgeDistribution, EthnicityDistribution, GenderDistribution},
+    estimate::AgeEstimate,
+    metric::SamplingMetrics,
+    reasoning::{EvidenceKind, ReasoningEntry},
+    r#type::{Attribute, SignalSource},
+};
 
 #[derive(Debug, Clone)]
 pub struct InferenceInput {
@@ -8,34 +17,44 @@ pub struct InferenceInput {
     pub name: Option<String>,
     pub profile_pic_url: Option<String>,
     pub browsing_history: Option<Vec<String>>,
+    pub declared: SelfIdentified,
 }
 
 #[derive(Debug, Clone)]
 pub struct InferenceSignal {
     pub source: SignalSource,
 
-    pub gender_male: f64,
-    pub gender_female: f64,
-    pub gender_other: f64,
+    pub gender: Option<GenderDistribution>,
+
+    pub ethnicity: Option<EthnicityDistribution>,
 
-    pub ethnicity: Option<String>,
-    pub ethnicity_confidence: f64,
+    pub age: Option<AgeDistribution>,
+    pub age_estimate: Option<AgeEstimate>,
 
-    pub age_group_under_18: f64,
-    pub age_group_18_24: f64,
-    pub age_group_25_34: f64,
-    pub age_group_35_44: f64,
-    pub age_group_45_54: f64,
-    pub age_group_55_64: f64,
-    pub age_group_65_plus: f64,
+    // ISO 3166-1 alpha-2 country code -> probability; may sum to < 1
+    pub region: BTreeMap<String, f64>,
 
     pub birth_year: Option<u16>,
+    // Stated, or plausible enough to pin the age exactly; a guessed year only
+    // shapes the age distribution
+    pub birth_year_certain: bool,
     pub organization: Option<OrganizationIntelligence>,
+    pub is_human: Option<bool>,
+
+    // Attributes stated by the person rather than inferred
+    pub self_reported: Vec<Attribute>,
+
+    pub reasoning: Vec<ReasoningEntry>,
+
+    pub sampling: Option<SamplingMetrics>,
 
-    pub reasoning: Vec<String>,
+    // Set when the agent could not produce a signal at all
+    pub failure: Option<AgentError>,
 
     pub latency_ms: u64,
     pub tokens_used: Option<u32>,
+    // Upstream calls made, retries included; None for agents that make none
+    pub attempts: Option<u32>,
 }
 
 impl InferenceSignal {
@@ -43,52 +62,105 @@ impl InferenceSignal {
         Self {
             source,
 
-            gender_male: 0.0,
-            gender_female: 0.0,
-            gender_other: 0.0,
+            gender: None,
 
             ethnicity: None,
-            ethnicity_confidence: 0.0,
 
-            age_group_under_18: 0.0,
-            age_group_18_24: 0.0,
-            age_group_25_34: 0.0,
-            age_group_35_44: 0.0,
-            age_group_45_54: 0.0,
-            age_group_55_64: 0.0,
-            age_group_65_plus: 0.0,
+            age: None,
+            age_estimate: None,
+
+            region: BTreeMap::new(),
 
             birth_year: None,
+            birth_year_certain: false,
             organization: None,
+            is_human: None,
+
+            self_reported: Vec::new(),
 
             reasoning: Vec::new(),
 
+            sampling: None,
+
+            failure: None,
+
             latency_ms: 0,
             tokens_used: None,
+            attempts: None,
         }
     }
 
-    pub fn set_age_probs(&mut self, probs: [f64; 7]) {
-        self.age_group_under_18 = probs[0];
-        self.age_group_18_24 = probs[1];
-        self.age_group_25_34 = probs[2];
-        self.age_group_35_44 = probs[3];
-        self.age_group_45_54 = probs[4];
-        self.age_group_55_64 = probs[5];
-        self.age_group_65_plus = probs[6];
+    pub fn failed(source: SignalSource, error: AgentError) -> Self {
+        let mut signal = Self::new(source);
+        signal.fail(error.code(), error.to_string());
+        signal.failure = Some(error);
+        signal
+    }
+
+    pub fn explain(
+        &mut self,
+        attribute: Option<Attribute>,
+        code: &str,
+        message: impl Into<String>,
+    ) {
+        let entry = ReasoningEntry::new(
+            self.source.clone(),
+            EvidenceKind::Evidence,
+            attribute,
+            code,
+            message,
+        );
+        self.reasoning.push(entry);
+    }
+
+    // One entry per attribute the evidence covers, so filtering by any of
+    // them keeps it; unattributed when it covers none
+    pub fn explain_each(
+        &mut self,
+        attributes: &[Attribute],
+        code: &str,
+        message: impl Into<String>,
+    ) {
+        let message = message.into();
+        if attributes.is_empty() {
+            return self.explain(None, code, message);
+        }
+        for attribute in attributes {
+            self.explain(Some(*attribute), code, message.clone());
+        }
+    }
+
+    pub fn warn(&mut self, attribute: Option<Attribute>, code: &str, message: impl Into<String>) {
+        let entry = ReasoningEntry::new(
+            self.source.clone(),
+            EvidenceKind::Warning,
+            attribute,
+            code,
+            message,
+        );
+        self.reasoning.push(entry);
+    }
+
+    pub fn fail(&mut self, code: &str, message: impl Into<String>) {
+        let entry = ReasoningEntry::new(
+            self.source.clone(),
+            EvidenceKind::Error,
+            None,
+            code,
+            message,
+        );
+        self.reasoning.push(entry);
     }
 
-    pub fn has_gender_signal(&self) -> bool {
-        self.gender_male > 0.0 || self.gender_female > 0.0 || self.gender_other > 0.0
+    pub fn has_region_signal(&self) -> bool {
+        self.region.values().any(|p| *p > 0.0)
     }
 
-    pub fn has_age_signal(&self) -> bool {
-        self.age_group_under_18 > 0.0
-            || self.age_group_18_24 > 0.0
-            || self.age_group_25_34 > 0.0
-            || self.age_group_35_44 > 0.0
-            || self.age_group_45_54 > 0.0
-            || self.age_group_55_64 > 0.0
-            || self.age_group_65_plus > 0.0
+    pub fn top_region(&self) -> Option<(&str, f64)> {
+        self.region
+            .iter()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .filter(|(_, p)| **p > 0.0)
+            .map(|(r, p)| (r.as_str(), *p))
     }
 }
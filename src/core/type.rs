@@ -1,19 +1,33 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-#[derive(Debug, Clone, Serialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum SignalSource {
     Local,
     Vision,
     Onomastic,
     Domain,
+    SelfReported,
+    Rules,
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Attribute {
+    Gender,
+    Age,
+    Ethnicity,
+    Region,
+    Organization,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum Gender {
     Male,
     Female,
+    Other,
     Undetermined,
 }
 
@@ -36,9 +50,48 @@ pub enum AgeGroup {
 }
 
 impl AgeGroup {
+    pub const ALL: [AgeGroup; 7] = [
+        Self::Under18,
+        Self::Age18_24,
+        Self::Age25_34,
+        Self::Age35_44,
+        Self::Age45_54,
+        Self::Age55_64,
+        Self::Age65Plus,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Under18 => "under_18",
+            Self::Age18_24 => "18-24",
+            Self::Age25_34 => "25-34",
+            Self::Age35_44 => "35-44",
+            Self::Age45_54 => "45-54",
+            Self::Age55_64 => "55-64",
+            Self::Age65Plus => "65+",
+        }
+    }
+
+    pub fn parse(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|g| g.label() == label)
+    }
+
+    // Ages covered, inclusive; the open-ended groups assume 13 and 90 as bounds
+    pub fn years(self) -> (u16, u16) {
+        match self {
+            Self::Under18 => (13, 17),
+            Self::Age18_24 => (18, 24),
+            Self::Age25_34 => (25, 34),
+            Self::Age35_44 => (35, 44),
+            Self::Age45_54 => (45, 54),
+            Self::Age55_64 => (55, 64),
+            Self::Age65Plus => (65, 90),
+        }
+    }
+
     pub fn from_age(age: i32) -> Self {
         match age {
-            0..=17 => Self::Under18,
+            ..=17 => Self::Under18,
             18..=24 => Self::Age18_24,
             25..=34 => Self::Age25_34,
             35..=44 => Self::Age35_44,
@@ -47,23 +100,46 @@ impl AgeGroup {
             _ => Self::Age65Plus,
         }
     }
+}
 
-    pub fn to_one_hot(&self) -> [f64; 7] {
-        let mut probs = [0.0; 7];
-        probs[*self as usize] = 1.0;
-        probs
-    }
+// Categories the onomastic agent may report
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Ethnicity {
+    SouthAsian,
+    EastAsian,
+    SoutheastAsian,
+    European,
+    African,
+    LatinAmerican,
+    MiddleEastern,
+}
 
-    pub fn blend(distributions: &[[f64; 7]], weights: &[f64]) -> [f64; 7] {
-        let mut result = [0.0; 7];
-        let total_weight: f64 = weights.iter().sum();
+impl Ethnicity {
+    pub const ALL: [Ethnicity; 7] = [
+        Self::SouthAsian,
+        Self::EastAsian,
+        Self::SoutheastAsian,
+        Self::European,
+        Self::African,
+        Self::LatinAmerican,
+        Self::MiddleEastern,
+    ];
 
-        for (dist, &w) in distributions.iter().zip(weights) {
-            for i in 0..7 {
-                result[i] += dist[i] * w / total_weight;
-            }
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SouthAsian => "south_asian",
+            Self::EastAsian => "east_asian",
+            Self::SoutheastAsian => "southeast_asian",
+            Self::European => "european",
+            Self::African => "african",
+            Self::LatinAmerican => "latin_american",
+            Self::MiddleEastern => "middle_eastern",
         }
-        result
+    }
+
+    pub fn parse(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|e| e.as_str() == label)
     }
 }
 
@@ -76,18 +152,7 @@ pub enum Confidence {
     None,
 }
 
-impl Confidence {
-    pub fn from_probability(p: f64) -> Self {
-        match p {
-            p if p >= 0.8 => Self::Strong,
-            p if p >= 0.5 => Self::Medium,
-            p if p > 0.0 => Self::Low,
-            _ => Self::None,
-        }
-    }
-}
-
-#[derive(Debug, Clone, Serialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
 pub struct OrganizationIntelligence {
     pub domain: String,
 
@@ -101,6 +166,52 @@ pub struct OrganizationIntelligence {
     pub employee_count: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub employee_count_source: Option<String>
+    pub employee_count_source: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hq_country: Option<String>,
+
+    // Which sources back each field, filled in when sources are merged
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub provenance: BTreeMap<String, FieldProvenance>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub conflicts: Vec<FieldConflict>,
+}
+
+impl OrganizationIntelligence {
+    pub fn new(domain: &str) -> Self {
+        Self {
+            domain: normalize_domain(domain),
+            ..Self::default()
+        }
+    }
 }
 
+// Lowercase, no surrounding whitespace, trailing dot or www. prefix
+pub fn normalize_domain(domain: &str) -> String {
+    let domain = domain.trim().trim_end_matches('.').to_lowercase();
+    match domain.strip_prefix("www.") {
+        Some(stripped) => stripped.to_string(),
+        None => domain,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct FieldProvenance {
+    pub sources: Vec<SignalSource>,
+    // Share of the sources reporting this field that agree with the value
+    pub confidence: f64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct FieldConflict {
+    pub field: String,
+    pub candidates: Vec<FieldCandidate>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct FieldCandidate {
+    pub source: SignalSource,
+    pub value: String,
+}
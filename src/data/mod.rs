@@ -1,3 +1,5 @@
 pub mod domain;
+pub mod tld;
 
 pub use domain::*;
+pub use tld::*;
@@ -0,0 +1,66 @@
+// Country-code TLDs commonly used for their country. Vanity ccTLDs
+// (.io, .ai, .co, .me, .tv, ...) are deliberately left out.
+pub const COUNTRY_CODE_TLDS: &[(&str, &str)] = &[
+    ("ae", "AE"),
+    ("ar", "AR"),
+    ("at", "AT"),
+    ("au", "AU"),
+    ("be", "BE"),
+    ("bg", "BG"),
+    ("br", "BR"),
+    ("ca", "CA"),
+    ("ch", "CH"),
+    ("cl", "CL"),
+    ("cn", "CN"),
+    ("cz", "CZ"),
+    ("de", "DE"),
+    ("dk", "DK"),
+    ("eg", "EG"),
+    ("es", "ES"),
+    ("fi", "FI"),
+    ("fr", "FR"),
+    ("gr", "GR"),
+    ("hk", "HK"),
+    ("hu", "HU"),
+    ("id", "ID"),
+    ("ie", "IE"),
+    ("il", "IL"),
+    ("in", "IN"),
+    ("it", "IT"),
+    ("jp", "JP"),
+    ("ke", "KE"),
+    ("kr", "KR"),
+    ("mx", "MX"),
+    ("my", "MY"),
+    ("ng", "NG"),
+    ("nl", "NL"),
+    ("no", "NO"),
+    ("nz", "NZ"),
+    ("pe", "PE"),
+    ("ph", "PH"),
+    ("pk", "PK"),
+    ("pl", "PL"),
+    ("pt", "PT"),
+    ("ro", "RO"),
+    ("ru", "RU"),
+    ("sa", "SA"),
+    ("se", "SE"),
+    ("sg", "SG"),
+    ("th", "TH"),
+    ("tr", "TR"),
+    ("tw", "TW"),
+    ("ua", "UA"),
+    ("uk", "GB"),
+    ("us", "US"),
+    ("vn", "VN"),
+    ("za", "ZA"),
+];
+
+pub fn country_for_tld(domain: &str) -> Option<&'static str> {
+    let tld = domain.rsplit('.').next()?.to_lowercase();
+
+    COUNTRY_CODE_TLDS
+        .iter()
+        .find(|(t, _)| *t == tld)
+        .map(|(_, country)| *country)
+}
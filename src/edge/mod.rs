@@ -0,0 +1,245 @@
+mod r#type;
+
+use crate::core::{Agreement, InferenceInput, InferenceSignal};
+
+pub use r#type::*;
+
+const KEYBOARD_ROWS: &[&str] = &["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+const MASH_RUN: usize = 4;
+const MAX_CONSONANT_RUN: usize = 6;
+// Letter groups that spell a single sound, as in "Schmidt" or "Nietzsche"
+const CLUSTERS: &[&str] = &["tsch", "sch", "ch", "ck", "gh", "ph", "sh", "th"];
+
+// Input-only checks, safe to run before any agent returns
+pub fn inspect_input(input: &InferenceInput) -> EdgeCase {
+    let mut edge = EdgeCase::default();
+
+    let name = input
+        .name
+        .as_deref()
+        .map(str::trim)
+        .filter(|n| !n.is_empty());
+    let local_part = input
+        .email
+        .as_deref()
+        .and_then(|e| e.split('@').next())
+        .map(|l| l.trim().to_lowercase())
+        .filter(|l| !l.is_empty());
+
+    if name.is_none() && local_part.is_none() {
+        edge.push(
+            EdgeCaseCode::EmptyInput,
+            "Neither a name nor an email was provided.",
+        );
+        return edge;
+    }
+
+    if let Some(name) = name {
+        if is_garbage_name(name) {
+            edge.push(
+                EdgeCaseCode::GarbageName,
+                format!("Name '{name}' is not a plausible personal name."),
+            );
+        } else if name
+            .split_whitespace()
+            .any(|t| is_keyboard_mash(&t.to_lowercase()))
+        {
+            edge.push(
+                EdgeCaseCode::KeyboardMash,
+                format!("Name '{name}' looks like keyboard mashing."),
+            );
+        }
+    }
+
+    if let Some(local) = &local_part {
+        let compact: String = local
+            .chars()
+            .filter(|c| !matches!(c, '.' | '_' | '-' | '+'))
+            .collect();
+
+        if !compact.is_empty() && compact.chars().all(|c| c.is_ascii_digit()) {
+            edge.push(
+                EdgeCaseCode::NumericLocalPart,
+                format!("Email local part '{local}' is numeric only."),
+            );
+        } else if tokens(local).iter().any(|t| is_keyboard_mash(t)) {
+            edge.push(
+                EdgeCaseCode::KeyboardMash,
+                format!("Email local part '{local}' looks like keyboard mashing."),
+            );
+        }
+
+        if let Some(name) = name
+            && !edge.has(EdgeCaseCode::GarbageName)
+            && is_name_email_mismatch(name, local)
+        {
+            edge.push(
+                EdgeCaseCode::NameEmailMismatch,
+                format!("Name '{name}' does not match email local part '{local}'."),
+            );
+        }
+    }
+
+    edge
+}
+
+// Checks that need the agents' output
+pub fn inspect_signals(edge: &mut EdgeCase, signals: &[InferenceSignal], agreement: &Agreement) {
+    if signals.iter().any(|s| s.is_human == Some(false)) {
+        edge.push(
+            EdgeCaseCode::NonHumanAvatar,
+            "Profile picture does not show a person.",
+        );
+    }
+
+    for d in &agreement.disagreements {
+        edge.push(
+            EdgeCaseCode::ContradictorySources,
+            format!(
+                "{:?} and {:?} disagree on {:?} (JS divergence {:.2}).",
+                d.sources[0], d.sources[1], d.attribute, d.divergence
+            ),
+        );
+    }
+}
+
+fn tokens(s: &str) -> Vec<String> {
+    s.split(|c: char| !c.is_alphabetic())
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn is_garbage_name(name: &str) -> bool {
+    let letters = name.chars().filter(|c| c.is_alphabetic()).count();
+    let total = name.chars().filter(|c| !c.is_whitespace()).count();
+
+    if letters < 2 || letters * 2 < total {
+        return true;
+    }
+
+    // "aaaa", "xxxxx"
+    let mut chars = name
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .flat_map(char::to_lowercase);
+    let first = chars.next();
+    letters >= 3 && chars.all(|c| Some(c) == first)
+}
+
+fn is_keyboard_mash(token: &str) -> bool {
+    if token.len() < MASH_RUN {
+        return false;
+    }
+
+    // Windows over characters, so a multi-byte letter is never split
+    let chars: Vec<char> = token.chars().collect();
+    let on_row = chars.windows(MASH_RUN).any(|w| {
+        let w: String = w.iter().collect();
+        KEYBOARD_ROWS.iter().any(|row| row.contains(&w))
+    });
+
+    let mut run = 0;
+    let mut longest = 0;
+    let mut rest = token;
+    while let Some(c) = rest.chars().next() {
+        let cluster = CLUSTERS.iter().find(|cl| rest.starts_with(**cl));
+        if c.is_ascii_alphabetic() && !"aeiouy".contains(c) {
+            run += 1;
+            longest = longest.max(run);
+        } else {
+            run = 0;
+        }
+        rest = &rest[cluster.map_or(c.len_utf8(), |cl| cl.len())..];
+    }
+
+    on_row || longest >= MAX_CONSONANT_RUN
+}
+
+fn is_name_email_mismatch(name: &str, local: &str) -> bool {
+    let local_tokens = tokens(local);
+
+    // Only judge local parts shaped like "first.last"
+    if local_tokens.len() < 2 || local_tokens.iter().any(|t| t.len() < 2) {
+        return false;
+    }
+
+    let name_tokens: Vec<String> = tokens(name).into_iter().filter(|t| t.len() >= 2).collect();
+    if name_tokens.is_empty() {
+        return false;
+    }
+
+    !name_tokens
+        .iter()
+        .any(|n| local.contains(n.as_str()) || local_tokens.iter().any(|l| n.contains(l.as_str())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::SelfIdentified;
+
+    fn input(name: Option<&str>, email: Option<&str>) -> InferenceInput {
+        InferenceInput {
+            email: email.map(String::from),
+            name: name.map(String::from),
+            profile_pic_url: None,
+            browsing_history: None,
+            declared: SelfIdentified::default(),
+        }
+    }
+
+    #[test]
+    fn clean_input_is_not_flagged() {
+        let edge = inspect_input(&input(Some("Jane Smith"), Some("jane.smith@company.com")));
+        assert!(!edge.flagged);
+    }
+
+    #[test]
+    fn flags_empty_input() {
+        let edge = inspect_input(&input(Some("  "), None));
+        assert!(edge.has(EdgeCaseCode::EmptyInput));
+    }
+
+    #[test]
+    fn flags_mash_and_numeric() {
+        assert!(
+            inspect_input(&input(None, Some("asdfgh@gmail.com"))).has(EdgeCaseCode::KeyboardMash)
+        );
+        assert!(
+            inspect_input(&input(None, Some("123.456@gmail.com")))
+                .has(EdgeCaseCode::NumericLocalPart)
+        );
+        assert!(inspect_input(&input(Some("???"), None)).has(EdgeCaseCode::GarbageName));
+    }
+
+    #[test]
+    fn consonant_clusters_are_not_mash() {
+        for email in ["jschmidt@company.com", "kschwartz@company.com"] {
+            assert!(!inspect_input(&input(None, Some(email))).flagged, "{email}");
+        }
+        for name in ["Anna Hirschberg", "Friedrich Nietzsche", "Knightsbridge"] {
+            assert!(!inspect_input(&input(Some(name), None)).flagged, "{name}");
+        }
+        assert!(
+            inspect_input(&input(None, Some("qwrtpsdf@gmail.com"))).has(EdgeCaseCode::KeyboardMash)
+        );
+        assert!(!inspect_input(&input(None, Some("john1234@gmail.com"))).flagged);
+    }
+
+    #[test]
+    fn accented_names_are_not_mash() {
+        for name in ["José García", "Zoë Müller", "Søren Kierkegaard"] {
+            assert!(!inspect_input(&input(Some(name), None)).flagged, "{name}");
+        }
+    }
+
+    #[test]
+    fn flags_name_email_mismatch() {
+        let edge = inspect_input(&input(Some("Jane Smith"), Some("john.doe@company.com")));
+        assert!(edge.has(EdgeCaseCode::NameEmailMismatch));
+
+        let edge = inspect_input(&input(Some("Jane Smith"), Some("jsmith@company.com")));
+        assert!(!edge.flagged);
+    }
+}
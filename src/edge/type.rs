@@ -0,0 +1,39 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeCaseCode {
+    EmptyInput,
+    GarbageName,
+    KeyboardMash,
+    NumericLocalPart,
+    NameEmailMismatch,
+    NonHumanAvatar,
+    ContradictorySources,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EdgeCaseReason {
+    pub code: EdgeCaseCode,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EdgeCase {
+    pub flagged: bool,
+    pub reasons: Vec<EdgeCaseReason>,
+}
+
+impl EdgeCase {
+    pub fn push(&mut self, code: EdgeCaseCode, detail: impl Into<String>) {
+        self.flagged = true;
+        self.reasons.push(EdgeCaseReason {
+            code,
+            detail: detail.into(),
+        });
+    }
+
+    pub fn has(&self, code: EdgeCaseCode) -> bool {
+        self.reasons.iter().any(|r| r.code == code)
+    }
+}
@@ -1,18 +1,47 @@
 mod agent;
 mod api;
+mod calibration;
+mod config;
 mod core;
 mod data;
+mod edge;
+mod rules;
 
 use ntex::web::{self, App};
 
 #[ntex::main]
 async fn main() -> std::io::Result<()> {
-    let api_key = std::env::var("OPENAI_API_KEY")
-        .expect("OPEN_API_KEY environment variable must be set.\n `export OPENAI_API_KEY='...'`");
+    if std::env::args().nth(1).as_deref() == Some("calibrate") {
+        return calibration::command::run(std::env::args().skip(2).collect());
+    }
+    if std::env::args().nth(1).as_deref() == Some("evaluate") {
+        return calibration::command::evaluate_command(std::env::args().skip(2).collect());
+    }
+
+    let config = config::Config::load();
+
+    let llm = agent::llm::LlmProviders::new(&config.llm, &config.agent_llm);
+    let calibration = config
+        .calibration_path
+        .as_deref()
+        .map(calibration::CalibrationSet::load)
+        .unwrap_or_default();
+    let rules = config
+        .rules_path
+        .as_deref()
+        .map(rules::RuleSet::load)
+        .unwrap_or_default();
+
+    let state = api::AppState {
+        llm,
+        config,
+        calibration,
+        rules,
+    };
 
     println!("AURA running on http://127.0.0.1:7878");
 
-    web::HttpServer::new(move || App::new().state(api_key.clone()).configure(api::configure))
+    web::HttpServer::new(move || App::new().state(state.clone()).configure(api::configure))
         .bind(("127.0.0.1", 7878))?
         .run()
         .await
@@ -0,0 +1,261 @@
+mod path;
+mod r#type;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::core::{
+    Attribute, InferenceInput, InferenceSignal, ReasoningEntry, SignalSource,
+    reasoning::EvidenceKind,
+};
+
+pub use r#type::{Action, Condition, Rule};
+
+// Operator-authored overrides applied to the fused signal, in file order
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RuleSet {
+    pub rules: Vec<Rule>,
+}
+
+impl RuleSet {
+    pub fn load(path: &str) -> Self {
+        let raw = std::fs::read_to_string(path)
+            .unwrap_or_else(|e| panic!("Cannot read rules file {path}: {e}"));
+
+        serde_json::from_str(&raw).unwrap_or_else(|e| panic!("Invalid rules file {path}: {e}"))
+    }
+
+    pub fn apply(
+        &self,
+        input: &InferenceInput,
+        signals: &[InferenceSignal],
+        fused: &mut InferenceSignal,
+    ) {
+        for rule in &self.rules {
+            let fires = rule
+                .when
+                .iter()
+                .all(|c| c.holds(c.field.read(input, signals, fused).as_ref()));
+            if !fires {
+                continue;
+            }
+
+            let mut failures = Vec::new();
+            for action in &rule.then {
+                let result = match action {
+                    Action::Set { field, value } => field.set(fused, value, input),
+                    Action::Clear { field } => field.clear(fused),
+                    Action::Scale { field, by } => field.scale(fused, *by),
+                };
+                if let Err(e) = result {
+                    failures.push((action.field().attribute(), e));
+                }
+            }
+
+            let message = match &rule.description {
+                Some(description) => format!("Rule {} applied: {description}", rule.id),
+                None => format!("Rule {} applied.", rule.id),
+            };
+            fused.reasoning.push(ReasoningEntry::new(
+                SignalSource::Rules,
+                EvidenceKind::Evidence,
+                rule.attribute(),
+                "rule_applied",
+                message,
+            ));
+
+            for (attribute, error) in failures {
+                fused.reasoning.push(ReasoningEntry::new(
+                    SignalSource::Rules,
+                    EvidenceKind::Warning,
+                    attribute,
+                    "rule_action_failed",
+                    format!("Rule {}: {error}", rule.id),
+                ));
+            }
+        }
+    }
+}
+
+impl Rule {
+    // The attribute every action touches, if there is just one
+    fn attribute(&self) -> Option<Attribute> {
+        let mut attributes = self.then.iter().map(|a| a.field().attribute());
+        let first = attributes.next()??;
+        attributes.all(|a| a == Some(first)).then_some(first)
+    }
+}
+
+impl Condition {
+    fn holds(&self, actual: Option<&Value>) -> bool {
+        if let Some(present) = self.present
+            && present != actual.is_some()
+        {
+            return false;
+        }
+
+        let Some(actual) = actual else {
+            // Only a bare presence check can hold for a missing field
+            return self.present == Some(false) && !self.compares();
+        };
+
+        let number = actual.as_f64();
+        let bound = |limit: Option<f64>, cmp: fn(f64, f64) -> bool| match limit {
+            Some(limit) => number.is_some_and(|n| cmp(n, limit)),
+            None => true,
+        };
+
+        self.equals
+            .as_ref()
+            .is_none_or(|expected| same(actual, expected))
+            && self
+                .matches
+                .as_ref()
+                .is_none_or(|re| actual.as_str().is_some_and(|s| re.is_match(s)))
+            && bound(self.gt, |n, l| n > l)
+            && bound(self.gte, |n, l| n >= l)
+            && bound(self.lt, |n, l| n < l)
+            && bound(self.lte, |n, l| n <= l)
+    }
+
+    fn compares(&self) -> bool {
+        self.equals.is_some()
+            || self.matches.is_some()
+            || [self.gt, self.gte, self.lt, self.lte]
+                .iter()
+                .any(Option::is_some)
+    }
+}
+
+// Strings compare case-insensitively and lists as sets
+fn same(actual: &Value, expected: &Value) -> bool {
+    match (actual, expected) {
+        (Value::String(a), Value::String(b)) => a.eq_ignore_ascii_case(b),
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().all(|x| b.iter().any(|y| same(x, y)))
+        }
+        (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        _ => actual == expected,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::core::{
+        AgeDistribution, AgeEstimate, AgeGroup, GenderDistribution, OrganizationIntelligence,
+        SelfIdentified,
+    };
+
+    fn input(email: &str) -> InferenceInput {
+        InferenceInput {
+            email: Some(email.to_string()),
+            name: None,
+            profile_pic_url: None,
+            browsing_history: None,
+            declared: SelfIdentified::default(),
+        }
+    }
+
+    fn rules(json: &str) -> RuleSet {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn fired_rule_overrides_and_explains() {
+        let rules = rules(
+            r#"{"rules": [{
+                "id": "school-domains",
+                "description": "School addresses belong to students",
+                "when": [{"field": "input.email_domain", "matches": "\\.edu$"}],
+                "then": [
+                    {"action": "set", "field": "age", "value": "18-24"},
+                    {"action": "clear", "field": "organization"}
+                ]
+            }]}"#,
+        );
+
+        let today = NaiveDate::from_ymd_opt(2026, 6, 1).unwrap();
+        let mut local = InferenceSignal::new(SignalSource::Local);
+        local.age = Some(AgeDistribution::from_group(AgeGroup::Age35_44));
+        local.birth_year = Some(1990);
+        local.birth_year_certain = true;
+        local.age_estimate = Some(AgeEstimate::from_birth_year(1990, today));
+        let mut fused = local.clone();
+
+        rules.apply(&input("jo@state.edu"), &[local], &mut fused);
+
+        let age = fused.age.unwrap();
+        assert_eq!(age.most_likely().0, AgeGroup::Age18_24);
+        // Nothing about the overridden year survives
+        assert_eq!(fused.birth_year, None);
+        assert_eq!(
+            fused.age_estimate,
+            Some(AgeEstimate::from_distribution(&age))
+        );
+        let entry = fused.reasoning.last().unwrap();
+        assert_eq!(entry.code, "rule_applied");
+        assert!(entry.message.contains("school-domains"));
+    }
+
+    #[test]
+    fn conditions_on_sources_and_probabilities() {
+        let rules = rules(
+            r#"{"rules": [{
+                "id": "name-only-gender",
+                "when": [
+                    {"field": "sources.gender", "equals": ["onomastic"]},
+                    {"field": "gender.female", "gte": 0.5}
+                ],
+                "then": [{"action": "scale", "field": "gender", "by": 0.5}]
+            }]}"#,
+        );
+
+        let mut onomastic = InferenceSignal::new(SignalSource::Onomastic);
+        onomastic.gender = Some(GenderDistribution::from_binary(0.0, 1.0).unwrap());
+        let mut fused = onomastic.clone();
+
+        rules.apply(&input("jo@example.com"), &[onomastic.clone()], &mut fused);
+        assert!(fused.gender.unwrap().female() < 1.0);
+
+        // A second source contributing gender stops the rule from firing
+        let mut vision = InferenceSignal::new(SignalSource::Vision);
+        vision.gender = onomastic.gender;
+        let mut fused = onomastic.clone();
+        rules.apply(&input("jo@example.com"), &[onomastic, vision], &mut fused);
+        assert_eq!(fused.gender.unwrap().female(), 1.0);
+        assert!(fused.reasoning.is_empty());
+    }
+
+    #[test]
+    fn conditions_on_organization_domain() {
+        let rules = rules(
+            r#"{"rules": [{
+                "id": "acme-staff",
+                "when": [{"field": "organization.domain", "equals": "acme.com"}],
+                "then": [{"action": "set", "field": "organization.category", "value": "Retail"}]
+            }]}"#,
+        );
+
+        let mut domain = InferenceSignal::new(SignalSource::Domain);
+        let mut org = OrganizationIntelligence::new("acme.com");
+        org.hq_country = Some("US".to_string());
+        domain.organization = Some(org);
+        let mut fused = domain.clone();
+
+        rules.apply(&input("jo@acme.com"), &[domain], &mut fused);
+        let org = fused.organization.unwrap();
+        assert_eq!(org.domain, "acme.com");
+        assert_eq!(org.category.as_deref(), Some("Retail"));
+    }
+
+    #[test]
+    fn unknown_field_is_rejected_at_load() {
+        let result = serde_json::from_str::<RuleSet>(
+            r#"{"rules": [{"id": "x", "then": [{"action": "clear", "field": "gender.unknown"}]}]}"#,
+        );
+        assert!(result.is_err());
+    }
+}
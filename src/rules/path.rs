@@ -0,0 +1,393 @@
+use serde_json::Value;
+
+use crate::core::{
+    AgeEstimate, AgeGroup, Attribute, Ethnicity, InferenceInput, InferenceSignal,
+    OrganizationIntelligence, distribution::Distribution,
+};
+
+const GENDERS: [&str; 3] = ["male", "female", "other"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputField {
+    Email,
+    EmailDomain,
+    EmailLocalPart,
+    Name,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrgField {
+    Domain,
+    Name,
+    Category,
+    EmployeeCount,
+    EmployeeCountSource,
+    HqCountry,
+}
+
+// A dotted field reference such as `input.email_domain` or `gender.female`
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(try_from = "String")]
+pub enum Path {
+    Input(InputField),
+    // Sources whose signal carried the attribute, before fusion
+    Sources(Attribute),
+    Gender(Option<usize>),
+    Age(Option<AgeGroup>),
+    Ethnicity(Option<Ethnicity>),
+    Region(Option<String>),
+    Organization(Option<OrgField>),
+    BirthYear,
+    IsHuman,
+}
+
+impl TryFrom<String> for Path {
+    type Error = String;
+
+    fn try_from(path: String) -> Result<Self, Self::Error> {
+        parse(&path).ok_or_else(|| format!("unknown field {path:?}"))
+    }
+}
+
+fn parse(path: &str) -> Option<Path> {
+    let (head, tail) = match path.split_once('.') {
+        Some((head, tail)) => (head, Some(tail)),
+        None => (path, None),
+    };
+
+    let parsed = match (head, tail) {
+        ("input", Some(field)) => Path::Input(match field {
+            "email" => InputField::Email,
+            "email_domain" => InputField::EmailDomain,
+            "email_local_part" => InputField::EmailLocalPart,
+            "name" => InputField::Name,
+            _ => return None,
+        }),
+        ("sources", Some(attribute)) => {
+            Path::Sources(serde_json::from_value(Value::String(attribute.to_string())).ok()?)
+        }
+        ("gender", None) => Path::Gender(None),
+        ("gender", Some(label)) => Path::Gender(Some(GENDERS.iter().position(|g| *g == label)?)),
+        ("age", None) => Path::Age(None),
+        ("age", Some(label)) => Path::Age(Some(
+            AgeGroup::ALL.into_iter().find(|g| g.label() == label)?,
+        )),
+        ("ethnicity", None) => Path::Ethnicity(None),
+        ("ethnicity", Some(label)) => Path::Ethnicity(Some(Ethnicity::parse(label)?)),
+        ("region", country) => Path::Region(country.map(str::to_uppercase)),
+        ("organization", None) => Path::Organization(None),
+        ("organization", Some(field)) => Path::Organization(Some(match field {
+            "domain" => OrgField::Domain,
+            "name" => OrgField::Name,
+            "category" => OrgField::Category,
+            "employee_count" => OrgField::EmployeeCount,
+            "employee_count_source" => OrgField::EmployeeCountSource,
+            "hq_country" => OrgField::HqCountry,
+            _ => return None,
+        })),
+        ("birth_year", None) => Path::BirthYear,
+        ("is_human", None) => Path::IsHuman,
+        _ => return None,
+    };
+
+    Some(parsed)
+}
+
+impl Path {
+    pub fn attribute(&self) -> Option<Attribute> {
+        match self {
+            Self::Input(_) => None,
+            Self::Sources(attribute) => Some(*attribute),
+            Self::Gender(_) => Some(Attribute::Gender),
+            Self::Age(_) | Self::BirthYear => Some(Attribute::Age),
+            Self::Ethnicity(_) => Some(Attribute::Ethnicity),
+            Self::Region(_) => Some(Attribute::Region),
+            Self::Organization(_) => Some(Attribute::Organization),
+            Self::IsHuman => None,
+        }
+    }
+
+    // Whole distributions read as their most likely label, outcomes as probabilities
+    pub fn read(
+        &self,
+        input: &InferenceInput,
+        signals: &[InferenceSignal],
+        signal: &InferenceSignal,
+    ) -> Option<Value> {
+        let value = match self {
+            Self::Input(field) => {
+                let email = input.email.as_deref().map(str::trim);
+                let text = match field {
+                    InputField::Email => email,
+                    InputField::EmailDomain => email.and_then(|e| e.split('@').nth(1)),
+                    InputField::EmailLocalPart => email.and_then(|e| e.split('@').next()),
+                    InputField::Name => input.name.as_deref().map(str::trim),
+                };
+                Value::from(text?)
+            }
+            Self::Sources(attribute) => signals
+                .iter()
+                .filter(|s| carries(s, *attribute))
+                .filter_map(|s| serde_json::to_value(&s.source).ok())
+                .collect(),
+            Self::Gender(None) => Value::from(GENDERS[signal.gender?.argmax().0]),
+            Self::Gender(Some(i)) => Value::from(signal.gender?.as_slice()[*i]),
+            Self::Age(None) => Value::from(signal.age?.most_likely().0.label()),
+            Self::Age(Some(group)) => Value::from(signal.age?.get(*group)),
+            Self::Ethnicity(None) => Value::from(signal.ethnicity?.most_likely().0.as_str()),
+            Self::Ethnicity(Some(e)) => Value::from(signal.ethnicity?.get(*e)),
+            Self::Region(None) => Value::from(signal.top_region()?.0),
+            Self::Region(Some(country)) => Value::from(*signal.region.get(country)?),
+            Self::Organization(field) => {
+                let org = signal.organization.as_ref()?;
+                let field = field.unwrap_or(OrgField::Domain);
+                Value::from(org_field(org, field)?)
+            }
+            Self::BirthYear => Value::from(signal.birth_year?),
+            Self::IsHuman => Value::from(signal.is_human?),
+        };
+
+        Some(value)
+    }
+
+    pub fn set(
+        &self,
+        signal: &mut InferenceSignal,
+        value: &Value,
+        input: &InferenceInput,
+    ) -> Result<(), String> {
+        let invalid = || format!("cannot set {self:?} to {value}");
+
+        match self {
+            Self::Gender(None) => {
+                let i = GENDERS.iter().position(|g| Some(*g) == value.as_str());
+                signal.gender = Some(Distribution::one_hot(i.ok_or_else(invalid)?));
+            }
+            Self::Gender(Some(i)) => {
+                let p = probability(value).ok_or_else(invalid)?;
+                signal.gender = pin(signal.gender.as_ref(), *i, p);
+            }
+            Self::Age(None) => {
+                let label = value.as_str().ok_or_else(invalid)?;
+                let group = AgeGroup::ALL.into_iter().find(|g| g.label() == label);
+                signal.age = Some(Distribution::from_group(group.ok_or_else(invalid)?));
+                age_rewritten(signal);
+            }
+            Self::Age(Some(group)) => {
+                let p = probability(value).ok_or_else(invalid)?;
+                signal.age = pin(signal.age.as_ref(), *group as usize, p);
+                age_rewritten(signal);
+            }
+            Self::Ethnicity(None) => {
+                let ethnicity = value.as_str().and_then(Ethnicity::parse);
+                signal.ethnicity = Some(Distribution::one_hot(
+                    ethnicity.ok_or_else(invalid)? as usize
+                ));
+            }
+            Self::Ethnicity(Some(e)) => {
+                let p = probability(value).ok_or_else(invalid)?;
+                signal.ethnicity = pin(signal.ethnicity.as_ref(), *e as usize, p);
+            }
+            Self::Region(Some(country)) => {
+                let p = probability(value).ok_or_else(invalid)?;
+                signal.region.insert(country.clone(), p);
+            }
+            Self::Organization(Some(field)) => {
+                let text = value.as_str().ok_or_else(invalid)?.to_string();
+                let org = signal.organization.get_or_insert_with(|| {
+                    let domain = input.email.as_deref().and_then(|e| e.split('@').nth(1));
+                    OrganizationIntelligence::new(domain.unwrap_or_default())
+                });
+                match org_field_mut(org, *field) {
+                    Some(slot) => *slot = Some(text),
+                    None => org.domain = text,
+                }
+            }
+            Self::BirthYear => {
+                let year = value.as_u64().and_then(|y| u16::try_from(y).ok());
+                signal.birth_year = Some(year.ok_or_else(invalid)?);
+                signal.birth_year_certain = true;
+            }
+            Self::IsHuman => signal.is_human = Some(value.as_bool().ok_or_else(invalid)?),
+            Self::Input(_) | Self::Sources(_) | Self::Region(None) | Self::Organization(None) => {
+                return Err(invalid());
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn clear(&self, signal: &mut InferenceSignal) -> Result<(), String> {
+        match self {
+            Self::Gender(None) => signal.gender = None,
+            Self::Gender(Some(i)) => signal.gender = reweight(signal.gender.as_ref(), *i, 0.0),
+            Self::Age(None) => {
+                signal.age = None;
+                age_rewritten(signal);
+            }
+            Self::Age(Some(group)) => {
+                signal.age = reweight(signal.age.as_ref(), *group as usize, 0.0);
+                age_rewritten(signal);
+            }
+            Self::Ethnicity(None) => signal.ethnicity = None,
+            Self::Ethnicity(Some(e)) => {
+                signal.ethnicity = reweight(signal.ethnicity.as_ref(), *e as usize, 0.0);
+            }
+            Self::Region(None) => signal.region.clear(),
+            Self::Region(Some(country)) => {
+                signal.region.remove(country);
+            }
+            Self::Organization(None) => signal.organization = None,
+            Self::Organization(Some(OrgField::Domain)) => {
+                return Err("organization.domain cannot be cleared".to_string());
+            }
+            Self::Organization(Some(field)) => {
+                if let Some(slot) = signal
+                    .organization
+                    .as_mut()
+                    .and_then(|org| org_field_mut(org, *field))
+                {
+                    *slot = None;
+                }
+            }
+            Self::BirthYear => {
+                signal.birth_year = None;
+                signal.birth_year_certain = false;
+            }
+            Self::IsHuman => signal.is_human = None,
+            Self::Input(_) | Self::Sources(_) => return Err(format!("cannot clear {self:?}")),
+        }
+
+        Ok(())
+    }
+
+    // Outcomes are scaled then renormalized; a whole distribution is pulled
+    // toward uniform, keeping `by` of its shape
+    pub fn scale(&self, signal: &mut InferenceSignal, by: f64) -> Result<(), String> {
+        if !(by.is_finite() && by >= 0.0) {
+            return Err(format!("invalid scale factor {by}"));
+        }
+
+        match self {
+            Self::Gender(None) => signal.gender = signal.gender.map(|d| flatten(&d, by)),
+            Self::Gender(Some(i)) => {
+                let p = signal.gender.map(|d| d.as_slice()[*i] * by);
+                signal.gender = p.and_then(|p| reweight(signal.gender.as_ref(), *i, p));
+            }
+            Self::Age(None) => {
+                signal.age = signal.age.map(|d| flatten(&d, by));
+                age_rewritten(signal);
+            }
+            Self::Age(Some(group)) => {
+                let p = signal.age.map(|d| d.get(*group) * by);
+                signal.age = p.and_then(|p| reweight(signal.age.as_ref(), *group as usize, p));
+                age_rewritten(signal);
+            }
+            Self::Ethnicity(None) => signal.ethnicity = signal.ethnicity.map(|d| flatten(&d, by)),
+            Self::Ethnicity(Some(e)) => {
+                let p = signal.ethnicity.map(|d| d.get(*e) * by);
+                signal.ethnicity =
+                    p.and_then(|p| reweight(signal.ethnicity.as_ref(), *e as usize, p));
+            }
+            Self::Region(None) => signal
+                .region
+                .values_mut()
+                .for_each(|p| *p = (*p * by).min(1.0)),
+            Self::Region(Some(country)) => {
+                if let Some(p) = signal.region.get_mut(country) {
+                    *p = (*p * by).min(1.0);
+                }
+            }
+            _ => return Err(format!("cannot scale {self:?}")),
+        }
+
+        Ok(())
+    }
+}
+
+fn carries(signal: &InferenceSignal, attribute: Attribute) -> bool {
+    match attribute {
+        Attribute::Gender => signal.gender.is_some(),
+        Attribute::Age => signal.age.is_some(),
+        Attribute::Ethnicity => signal.ethnicity.is_some(),
+        Attribute::Region => signal.has_region_signal(),
+        Attribute::Organization => signal.organization.is_some(),
+    }
+}
+
+// The birth year no longer agrees with the rewritten buckets, and the estimate
+// must follow them
+fn age_rewritten(signal: &mut InferenceSignal) {
+    signal.age_estimate = signal.age.as_ref().map(AgeEstimate::from_distribution);
+    signal.birth_year = None;
+    signal.birth_year_certain = false;
+}
+
+fn probability(value: &Value) -> Option<f64> {
+    value.as_f64().filter(|p| (0.0..=1.0).contains(p))
+}
+
+fn org_field(org: &OrganizationIntelligence, field: OrgField) -> Option<&str> {
+    match field {
+        OrgField::Domain => Some(&org.domain),
+        OrgField::Name => org.name.as_deref(),
+        OrgField::Category => org.category.as_deref(),
+        OrgField::EmployeeCount => org.employee_count.as_deref(),
+        OrgField::EmployeeCountSource => org.employee_count_source.as_deref(),
+        OrgField::HqCountry => org.hq_country.as_deref(),
+    }
+}
+
+// The optional fields only; the domain is always present
+fn org_field_mut(
+    org: &mut OrganizationIntelligence,
+    field: OrgField,
+) -> Option<&mut Option<String>> {
+    match field {
+        OrgField::Domain => None,
+        OrgField::Name => Some(&mut org.name),
+        OrgField::Category => Some(&mut org.category),
+        OrgField::EmployeeCount => Some(&mut org.employee_count),
+        OrgField::EmployeeCountSource => Some(&mut org.employee_count_source),
+        OrgField::HqCountry => Some(&mut org.hq_country),
+    }
+}
+
+// Replaces one outcome's weight and renormalizes; None once nothing is left
+fn reweight<const N: usize, K>(
+    dist: Option<&Distribution<N, K>>,
+    index: usize,
+    weight: f64,
+) -> Option<Distribution<N, K>> {
+    let mut probs: [f64; N] = dist?.as_slice().try_into().ok()?;
+    probs[index] = weight;
+    Distribution::new(probs).ok()
+}
+
+// Fixes one outcome at `p`, rescaling the others to fill the rest
+fn pin<const N: usize, K>(
+    dist: Option<&Distribution<N, K>>,
+    index: usize,
+    p: f64,
+) -> Option<Distribution<N, K>> {
+    let mut probs = [0.0; N];
+    if let Some(dist) = dist {
+        probs.copy_from_slice(dist.as_slice());
+    }
+
+    let rest: f64 = probs.iter().sum::<f64>() - probs[index];
+    for (i, q) in probs.iter_mut().enumerate() {
+        *q = match i {
+            i if i == index => p,
+            _ if rest > 0.0 => *q * (1.0 - p) / rest,
+            _ => (1.0 - p) / (N - 1) as f64,
+        };
+    }
+    Distribution::new(probs).ok()
+}
+
+fn flatten<const N: usize, K: Clone>(dist: &Distribution<N, K>, by: f64) -> Distribution<N, K> {
+    let by = by.min(1.0);
+    dist.map(|p| by * p + (1.0 - by) / N as f64)
+        .unwrap_or_else(|_| dist.clone())
+}
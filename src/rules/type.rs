@@ -0,0 +1,56 @@
+use regex::Regex;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+use super::path::Path;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Rule {
+    pub id: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    // All conditions must hold; an empty list always fires
+    #[serde(default)]
+    pub when: Vec<Condition>,
+    pub then: Vec<Action>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Condition {
+    pub field: Path,
+    pub equals: Option<Value>,
+    #[serde(default, deserialize_with = "regex")]
+    pub matches: Option<Regex>,
+    pub gt: Option<f64>,
+    pub gte: Option<f64>,
+    pub lt: Option<f64>,
+    pub lte: Option<f64>,
+    pub present: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Action {
+    Set { field: Path, value: Value },
+    Clear { field: Path },
+    Scale { field: Path, by: f64 },
+}
+
+impl Action {
+    pub fn field(&self) -> &Path {
+        match self {
+            Self::Set { field, .. } | Self::Clear { field } | Self::Scale { field, .. } => field,
+        }
+    }
+}
+
+fn regex<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Regex>, D::Error> {
+    let Some(pattern) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+
+    Regex::new(&pattern)
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}